@@ -0,0 +1,44 @@
+//! Compare the speed of the steppers on a large random board.
+//!
+//! Run with `cargo run --release --example bench`, adding `--features simd` to include the SIMD
+//! stepper.
+
+use std::{sync::{Arc, RwLock}, time::Instant};
+
+use game_of_life::{rule::Rule, utils};
+
+/// The side length of the board.
+static SIZE: usize = 1024;
+/// The number of generations timed for each stepper.
+static GENERATIONS: u32 = 20;
+
+/// Time a stepper over `GENERATIONS` generations and print the mean time per generation.
+fn bench(label: &str, mut step: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..GENERATIONS { step(); }
+    println!("{:<24} {:>10} us per generation", label, start.elapsed().as_micros() / GENERATIONS as u128);
+}
+
+fn main() {
+    let initial: ndarray::Array2<u8> = utils::random_board(SIZE, SIZE, 0.3, 1);
+    let threads = std::thread::available_parallelism().map_or(4, |n| n.get());
+
+    let mut brd = initial.clone();
+    bench("next_generation", || brd = utils::next_generation(&brd));
+
+    let shared = Arc::new(RwLock::new(initial.clone()));
+    let extents = utils::get_subgrids(SIZE, SIZE);
+    bench("step_parallel", || { utils::step_parallel(&shared, &SIZE, &SIZE, &extents, &Rule::conway()); });
+
+    let mut brd = initial.clone();
+    bench("step_rows_parallel", || brd = utils::step_rows_parallel(&brd, threads));
+
+    let mut brd = initial.clone();
+    bench("step_padded", || brd = utils::step_padded(&brd));
+
+    #[cfg(feature = "simd")]
+    {
+        let mut brd = initial.clone();
+        bench("next_generation_simd", || brd = game_of_life::simd::next_generation_simd(&brd, &Rule::conway()));
+    }
+}
@@ -1,88 +1,566 @@
-use ndarray_csv::Array2Reader;
-use std::{thread::{sleep, JoinHandle}, time::Duration, thread, sync::RwLock, sync::Arc, sync::mpsc};
+#![allow(clippy::needless_return)]
 
-use game_of_life::utils;
+use std::{io::{Read, Write}, thread::sleep, time::{Duration, Instant}, sync::RwLock, sync::Arc};
 
-fn main() {
-    // Get the current path, go up 3 directories, then find the board.
-    let mut csv_path: std::path::PathBuf = std::env::current_exe().unwrap();
-    for _ in 0..3 { csv_path.pop(); }
-    for val in vec!["src", "board.csv"] { csv_path.push(val); }
-
-    // Build the csv reader.
-    let mut reader = csv::ReaderBuilder::new().has_headers(false)
-                                              .from_path(csv_path)
-                                              .expect("Cannot read file");
-    // Use the csv reader to obtain the starting board.
-    let starting_board: ndarray::Array2<u8> = reader.deserialize_array2_dynamic().unwrap();
-    // Prepare the data for parallel read operations.
-    // to_owned returns a copy.
-    // RwLock allows multiple simultaneous read access, and single write access.
-    // 'Automatically Reference Counted' allows for multiple parallel references to exist.
-    let data_board: Arc<RwLock<ndarray::Array2<u8>>> = Arc::new(RwLock::new(starting_board.to_owned()));
-
-    // The shape of the board.
-    let rows: usize = starting_board.shape()[0];
-    let cols: usize = starting_board.shape()[1];
-
-    // Create sub-grids.
-    let extents: Vec<(usize, usize, usize, usize)> = utils::get_subgrids(rows, cols);
-
-    // Create an array of thread handles.
-    let mut handles: Vec<thread::JoinHandle<_>> = vec![];
-    let mut h: JoinHandle<()>;
-
-    for iter in 0..50 {
-        // Create a send / receive pair. A sender will calculate the moves for a particular subgrid
-        // and send them to the receiver.
-        let (tx, rx): (mpsc::Sender<(usize, usize, u8)>,
-                       mpsc::Receiver<(usize, usize, u8)>) = mpsc::channel();
-
-        // Clear screen, sleep, and print the board in a context block for the RwLock.
-        print!("{}[2J", 27 as char);
-        sleep(Duration::from_millis(100 as u64));
-        { utils::print_board(&(data_board.read().unwrap()), &rows, &cols, &iter); }
-
-        // Loop over subgrids.
-        for &(r0, rl, c0, cl) in &extents {
-            // Use Arc to create a new reference to the board.
-            let par_data = data_board.clone();
-            // Create a new reference to the sender.
-            let par_tx = (&tx).clone();
-            handles.push(
-                // Create a thread that takes a subgrid (board and boundaries), finds the moves
-                // and sends them to the receiver.
-                thread::spawn(move ||
-                    {
-                        for mv in utils::capture_moves(&(par_data.read().unwrap()),
-                                                       &rows,
-                                                       &cols,
-                                                       &r0,
-                                                       &rl,
-                                                       &c0,
-                                                       &cl) { par_tx.send(mv).unwrap(); }
-                    }
-                )
-            );
+use std::io::IsTerminal;
+
+use game_of_life::{render::{AgeRenderer, DeltaRenderer, Palette}, utils};
+
+/// Command line options.
+struct Options {
+    /// The rule, boundary and neighborhood.
+    config: utils::StepConfig,
+    /// Write raw RGB24 frames to stdout instead of drawing to the terminal.
+    raw_frames: bool,
+    /// The side length of each cell in pixels, for raw frames.
+    scale: usize,
+    /// Print the time spent stepping and rendering each generation, and a summary at the end.
+    profile: bool,
+    /// How many past generations to compare against when looking for oscillators. Each one keeps
+    /// a copy of the board in memory.
+    detect_period: Option<usize>,
+    /// Stop once the board is a still life or an oscillator.
+    stop_on_stable: bool,
+    /// The most generations to run. Any of the stop conditions can end the run sooner.
+    generations: usize,
+    /// Stop once the population crosses this many cells, whichever of this or `generations`
+    /// comes first. With several boards, every board has to cross it.
+    stop_at_population: Option<usize>,
+    /// Whether the population has to fall to `stop_at_population` or below, rather than rise to
+    /// it or above. Rising is the default.
+    stop_below: Option<bool>,
+    /// The pause before drawing each frame. 0 means as fast as the terminal can render.
+    delay_ms: u64,
+    /// Draw this many frames a second instead of pausing for `delay_ms`, sleeping for whatever is
+    /// left of each frame after stepping and drawing. When a frame runs over and the simulation
+    /// falls a whole frame behind, generations are stepped without being drawn until it catches
+    /// up, so the board moves at the same pace however slow the terminal is.
+    fps: Option<u32>,
+    /// The CSV files to load boards from, drawn side by side. Empty means the bundled board.
+    boards: Vec<std::path::PathBuf>,
+    /// Start from a board written inline, such as `010/111/100`, instead of loading one.
+    cells: Option<ndarray::Array2<u8>>,
+    /// Start from an all-dead board of `rows` by `cols` instead of loading one.
+    empty: bool,
+    /// Start from a random board of `rows` by `cols` instead of loading one.
+    random: bool,
+    rows: Option<usize>,
+    cols: Option<usize>,
+    /// The chance of each cell of a random board being alive, 0.5 by default.
+    density: Option<f64>,
+    /// The seed of a random board. Without one, a seed is picked and printed so the run can be
+    /// repeated.
+    seed: Option<u64>,
+    /// Edit the starting board in the terminal before running it.
+    edit: bool,
+    /// The colors of live cells by age, in the terminal.
+    palette: &'static Palette,
+    /// Label every this many rows and columns with their index, in the terminal.
+    grid_labels: Option<usize>,
+    /// Redraw only the cells that changed since the last frame, in the terminal.
+    delta: bool,
+}
+
+/// Parse the command line, exiting with a message on invalid input.
+fn parse_args() -> Options {
+    let mut opts = Options { config: utils::StepConfig::default(), raw_frames: false, scale: 4, profile: false,
+                             detect_period: None, stop_on_stable: false, generations: 50,
+                             stop_at_population: None, stop_below: None, delay_ms: 100, fps: None,
+                             boards: vec![], cells: None, empty: false, random: false,
+                             rows: None, cols: None, density: None, seed: None,
+                             edit: false, palette: &game_of_life::render::MONO, grid_labels: None, delta: false };
+    // --delay-ms has a default, so remember whether it was given to check it against --fps.
+    let mut delay_given = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rule" => opts.config.rule = parse_value(&mut args, &arg),
+            "--boundary" => {
+                let name: String = parse_value(&mut args, &arg);
+                opts.config.boundary = match name.as_str() {
+                    "fixed" => utils::Boundary::Fixed,
+                    "torus" => utils::Boundary::Torus,
+                    "fixed-alive" => utils::Boundary::FixedAlive,
+                    _ => exit_with(&format!("unknown boundary '{}', expected fixed, torus or fixed-alive", name)),
+                };
+            }
+            "--neighborhood" => {
+                let name: String = parse_value(&mut args, &arg);
+                opts.config.neighborhood = match name.as_str() {
+                    "moore" => utils::Neighborhood::Moore,
+                    "von-neumann" => utils::Neighborhood::VonNeumann,
+                    _ => exit_with(&format!("unknown neighborhood '{}', expected moore or von-neumann", name)),
+                };
+            }
+            "--radius" => {
+                opts.config.radius = parse_value(&mut args, &arg);
+                // A B/S rule only knows counts up to 8, and every larger neighborhood has more
+                // cells than that.
+                if opts.config.radius != 1 { exit_with("--radius must be 1, --rule takes counts of at most 8 neighbors"); }
+            }
+            "--raw-frames" => opts.raw_frames = true,
+            "--scale" => {
+                opts.scale = parse_value(&mut args, &arg);
+                if opts.scale == 0 { exit_with("--scale must be at least 1"); }
+            }
+            "--profile" => opts.profile = true,
+            "--detect-period" => {
+                let period: usize = parse_value(&mut args, &arg);
+                if period == 0 { exit_with("--detect-period must be at least 1"); }
+                opts.detect_period = Some(period);
+            }
+            "--stop-on-stable" => opts.stop_on_stable = true,
+            "--edit" => opts.edit = true,
+            "--generations" => opts.generations = parse_value(&mut args, &arg),
+            "--stop-at-population" => opts.stop_at_population = Some(parse_value(&mut args, &arg)),
+            "--stop-above" => opts.stop_below = Some(false),
+            "--stop-below" => opts.stop_below = Some(true),
+            "--delay-ms" => {
+                opts.delay_ms = parse_value(&mut args, &arg);
+                delay_given = true;
+            }
+            "--fps" => {
+                let fps: u32 = parse_value(&mut args, &arg);
+                if fps == 0 { exit_with("--fps must be at least 1"); }
+                opts.fps = Some(fps);
+            }
+            "--board" => opts.boards.push(parse_value(&mut args, &arg)),
+            "--cells" => {
+                let text: String = parse_value(&mut args, &arg);
+                let cells = utils::board_from_string(&text)
+                    .unwrap_or_else(|e| exit_with(&format!("invalid value '{}' for --cells: {}", text, e)));
+                opts.cells = Some(cells);
+            }
+            "--empty" => opts.empty = true,
+            "--random" => opts.random = true,
+            "--density" => {
+                let density: f64 = parse_value(&mut args, &arg);
+                if !(0.0..=1.0).contains(&density) { exit_with("--density must be between 0 and 1"); }
+                opts.density = Some(density);
+            }
+            "--seed" => opts.seed = Some(parse_value(&mut args, &arg)),
+            "--rows" => opts.rows = Some(parse_value(&mut args, &arg)),
+            "--cols" => opts.cols = Some(parse_value(&mut args, &arg)),
+            "--delta" => opts.delta = true,
+            "--grid-labels" => {
+                let every: usize = parse_value(&mut args, &arg);
+                if every == 0 { exit_with("--grid-labels must be at least 1"); }
+                opts.grid_labels = Some(every);
+            }
+            "--color-scheme" => {
+                let name: String = parse_value(&mut args, &arg);
+                opts.palette = Palette::named(&name).unwrap_or_else(|| {
+                    let names: Vec<&str> = game_of_life::render::PALETTES.iter().map(|p| p.name).collect();
+                    exit_with(&format!("unknown color scheme '{}', expected one of {}", name, names.join(", ")))
+                });
+            }
+            _ => exit_with(&format!("unknown argument '{}'", arg)),
+        }
+    }
+    if opts.stop_below.is_some() & opts.stop_at_population.is_none() {
+        exit_with("--stop-above and --stop-below require --stop-at-population");
+    }
+    if opts.raw_frames & (opts.boards.len() > 1) { exit_with("--raw-frames supports a single --board"); }
+    if delay_given & opts.fps.is_some() { exit_with("--fps cannot be combined with --delay-ms"); }
+    if opts.edit {
+        if opts.raw_frames | (opts.boards.len() > 1) { exit_with("--edit supports a single --board and no --raw-frames"); }
+        if !std::io::stdin().is_terminal() | !std::io::stdout().is_terminal() { exit_with("--edit needs a terminal"); }
+    }
+    if opts.cells.is_some() & (opts.empty | !opts.boards.is_empty()) {
+        exit_with("--cells cannot be combined with --board or --empty");
+    }
+    if opts.random & (opts.empty | opts.cells.is_some() | !opts.boards.is_empty()) {
+        exit_with("--random cannot be combined with --board, --cells or --empty");
+    }
+    if opts.empty | opts.random {
+        if !opts.boards.is_empty() { exit_with("--empty cannot be combined with --board"); }
+        match (opts.rows, opts.cols) {
+            (Some(rows), Some(cols)) if (rows > 0) & (cols > 0) => {}
+            _ => exit_with("--empty and --random require --rows and --cols of at least 1"),
+        }
+    }
+    else if opts.rows.is_some() | opts.cols.is_some() { exit_with("--rows and --cols require --empty or --random"); }
+    if !opts.random & (opts.seed.is_some() | opts.density.is_some()) { exit_with("--seed and --density require --random"); }
+    if !opts.palette.colors.is_empty() & (opts.boards.len() > 1) { exit_with("--color-scheme supports a single --board"); }
+    if opts.grid_labels.is_some() & (!opts.palette.colors.is_empty() | (opts.boards.len() > 1)) {
+        exit_with("--grid-labels supports a single --board and no --color-scheme");
+    }
+    if opts.delta & (opts.raw_frames | opts.grid_labels.is_some() | !opts.palette.colors.is_empty() | (opts.boards.len() > 1)) {
+        exit_with("--delta supports a single --board and no --raw-frames, --grid-labels or --color-scheme");
+    }
+    if !supports_color() { opts.palette = &game_of_life::render::MONO; }
+    return opts
+}
+
+/// Parse the value following a flag, exiting with a message if it is missing or invalid.
+fn parse_value<T>(args: &mut impl Iterator<Item = String>, flag: &str) -> T
+    where T: std::str::FromStr, T::Err: std::fmt::Display {
+    let value = args.next().unwrap_or_else(|| exit_with(&format!("{} requires a value", flag)));
+    return value.parse().unwrap_or_else(|e| exit_with(&format!("invalid value '{}' for {}: {}", value, flag, e)))
+}
+
+/// Print the min, max and mean of a series of per-generation timings.
+fn print_timing_summary(label: &str, timings: &[u128]) {
+    if timings.is_empty() { return; }
+    let min = timings.iter().min().unwrap();
+    let max = timings.iter().max().unwrap();
+    let mean = timings.iter().sum::<u128>() / timings.len() as u128;
+    eprintln!("{}: min {} ns, max {} ns, mean {} ns per generation", label, min, max, mean);
+}
+
+/// Whether stdout is a terminal that can show colors. `NO_COLOR` turns colors off, as does a dumb
+/// or unknown terminal.
+fn supports_color() -> bool {
+    let term = std::env::var("TERM").unwrap_or_default();
+    return std::env::var_os("NO_COLOR").is_none() & !term.is_empty() & (term != "dumb") & std::io::stdout().is_terminal()
+}
+
+/// Print an error and exit with a nonzero status.
+fn exit_with(msg: &str) -> ! {
+    eprintln!("error: {}", msg);
+    std::process::exit(2);
+}
+
+/// The rows and columns of the terminal drawn to, or `None` if stdout isn't a terminal. Cheap
+/// enough to ask every frame, unlike `stty size`.
+#[cfg(unix)]
+fn terminal_size() -> Option<(u16, u16)> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    // TIOCGWINSZ only writes a winsize.
+    let ret = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+    return if ret == 0 { Some((size.ws_row, size.ws_col)) } else { None }
+}
+
+/// Without unix terminals, the size is never known, so resizes aren't noticed.
+#[cfg(not(unix))]
+fn terminal_size() -> Option<(u16, u16)> {
+    return None
+}
+
+/// Run `stty` on the terminal, returning what it prints.
+fn stty(args: &[&str]) -> std::io::Result<String> {
+    let output = std::process::Command::new("stty").args(args).stdin(std::process::Stdio::inherit()).output()?;
+    if !output.status.success() { return Err(std::io::Error::other(String::from_utf8_lossy(&output.stderr).into_owned())); }
+    return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Puts the terminal in raw mode, so keys are read as they are pressed, and restores it when
+/// dropped, even when unwinding from a panic.
+struct RawTerminal {
+    /// The settings from before, in `stty -g` form.
+    saved: String,
+}
+
+impl RawTerminal {
+    fn enable() -> std::io::Result<RawTerminal> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        // Hide the terminal's own cursor.
+        print!("\x1b[?25l");
+        return Ok(RawTerminal { saved })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h");
+        let _ = std::io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// A key pressed in the editor.
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Char(u8),
+}
+
+/// Read the next key, turning arrow key escape sequences into a single key.
+fn read_key(input: &mut impl Iterator<Item = u8>) -> Option<Key> {
+    let byte = input.next()?;
+    if byte != 0x1b { return Some(Key::Char(byte)); }
+    if input.next()? != b'[' { return Some(Key::Char(0x1b)); }
+    return Some(match input.next()? {
+        b'A' => Key::Up,
+        b'B' => Key::Down,
+        b'C' => Key::Right,
+        b'D' => Key::Left,
+        other => Key::Char(other),
+    })
+}
+
+/// Let the user edit a board in the terminal, with a cursor moved by the arrow keys. Space toggles
+/// the cell under the cursor, `s` saves the board as CSV to `save_path`, enter returns the board
+/// to be run, and `q` or ctrl-c quits.
+fn edit(mut brd: ndarray::Array2<u8>, save_path: &std::path::Path) -> ndarray::Array2<u8> {
+    let (rows, cols) = brd.dim();
+    let raw = RawTerminal::enable().unwrap_or_else(|e| exit_with(&format!("cannot set up the terminal: {}", e)));
+    let mut input = std::io::stdin().lock().bytes().map_while(Result::ok);
+    let (mut r, mut c) = (0, 0);
+    let mut status = String::new();
+    loop {
+        // Raw mode doesn't return to the start of the line on a newline, so end lines in "\r\n".
+        let mut frame = String::from("\x1b[2J\x1b[H");
+        for (br, row) in brd.genrows().into_iter().enumerate() {
+            for (bc, v) in row.iter().enumerate() {
+                if (br, bc) == (r, c) { frame.push_str(&format!("\x1b[7m{}\x1b[0m ", v)); }
+                else { frame.push_str(&format!("{} ", v)); }
+            }
+            frame.push_str("\r\n");
         }
+        frame.push_str("arrows: move, space: toggle, s: save, enter: run, q: quit\r\n");
+        frame.push_str(&status);
+        print!("{}", frame);
+        std::io::stdout().flush().unwrap();
 
-        // Join all the threads.
-        while handles.len() > 0 {
-            h = handles.pop().unwrap();
-            h.join().unwrap();
+        match read_key(&mut input) {
+            Some(Key::Up) => r = r.saturating_sub(1),
+            Some(Key::Down) => r = (r + 1).min(rows - 1),
+            Some(Key::Left) => c = c.saturating_sub(1),
+            Some(Key::Right) => c = (c + 1).min(cols - 1),
+            Some(Key::Char(b' ')) => brd[[r, c]] = 1 - brd[[r, c]].min(1),
+            Some(Key::Char(b's')) => status = match utils::save_board(&brd, save_path) {
+                Ok(()) => format!("saved to {}", save_path.display()),
+                Err(e) => format!("cannot save to {}: {}", save_path.display(), e),
+            },
+            Some(Key::Char(b'\r')) | Some(Key::Char(b'\n')) => break,
+            Some(Key::Char(b'q')) | Some(Key::Char(3)) | None => {
+                drop(raw);
+                println!();
+                std::process::exit(0);
+            }
+            Some(Key::Char(_)) => {}
         }
+    }
+    drop(raw);
+    return brd
+}
+
+/// A board being simulated, shared with the threads that step it.
+struct Simulation {
+    /// RwLock allows multiple simultaneous read access, and single write access.
+    /// 'Automatically Reference Counted' allows for multiple parallel references to exist.
+    board: Arc<RwLock<ndarray::Array2<u8>>>,
+    rows: usize,
+    cols: usize,
+    /// The sub-grids stepped in parallel.
+    extents: Vec<(usize, usize, usize, usize)>,
+    /// Past boards, if still lifes and oscillators are being looked for.
+    detector: Option<utils::CycleDetector>,
+    /// The period, once the board is a still life or an oscillator.
+    period: Option<usize>,
+}
+
+impl Simulation {
+    fn new(starting_board: ndarray::Array2<u8>, opts: &Options) -> Simulation {
+        // The shape of the board.
+        let rows: usize = starting_board.shape()[0];
+        let cols: usize = starting_board.shape()[1];
 
-        // Drop the sender; otherwise looping over received data will hang.
-        drop(tx);
+        // Only keep past boards around if something will look at them.
+        let detector = if opts.stop_on_stable | opts.detect_period.is_some() {
+            let mut detector = utils::CycleDetector::new(opts.detect_period.unwrap_or(1));
+            detector.push(&starting_board);
+            Some(detector)
+        } else { None };
 
-        // Loop over data in the receiver.
-        for mv in &rx {
-            // Write the change to the board.
-            let (r, c, v) = mv;
-            // Modify the data in a context block for the RwLock.
-            { data_board.write().unwrap()[[r, c]] = v; }
+        return Simulation {
+            board: Arc::new(RwLock::new(starting_board)),
+            rows,
+            cols,
+            // Create sub-grids.
+            extents: utils::get_subgrids(rows, cols),
+            detector,
+            period: None,
         }
     }
+
+    /// Step every subgrid in parallel and write the changes to the board. Boundaries and
+    /// neighborhoods other than the standard ones are stepped on a single thread.
+    fn step(&mut self, config: &utils::StepConfig) {
+        if config.is_standard() {
+            utils::step_parallel(&self.board, &self.rows, &self.cols, &self.extents, &config.rule);
+        }
+        else {
+            let mut brd = self.board.write().unwrap();
+            *brd = utils::step(&brd, config);
+        }
+    }
+
+    /// Look for a still life or oscillator, returning the period the first time one is found.
+    fn detect(&mut self) -> Option<usize> {
+        let detector = self.detector.as_mut()?;
+        let period = detector.push(&(self.board.read().unwrap()));
+        if self.period.is_some() { return None; }
+        self.period = period;
+        return period
+    }
 }
 
+fn main() {
+    // Validate the arguments before doing any work.
+    let opts = parse_args();
+
+    let mut sims: Vec<Simulation> = if let Some(cells) = &opts.cells {
+        vec![Simulation::new(cells.clone(), &opts)]
+    }
+    else if opts.random {
+        // Always report the seed, so an interesting soup can be run again.
+        let seed = opts.seed.unwrap_or_else(rand::random);
+        eprintln!("seed: {}", seed);
+        let starting_board = utils::random_board(opts.rows.unwrap(), opts.cols.unwrap(), opts.density.unwrap_or(0.5), seed);
+        vec![Simulation::new(starting_board, &opts)]
+    }
+    else if opts.empty {
+        // Start from an all-dead board.
+        let starting_board = ndarray::Array2::zeros((opts.rows.unwrap(), opts.cols.unwrap()));
+        vec![Simulation::new(starting_board, &opts)]
+    }
+    else {
+        let mut paths: Vec<std::path::PathBuf> = opts.boards.clone();
+        if paths.is_empty() {
+            // Get the current path, go up 3 directories, then find the board.
+            let mut csv_path: std::path::PathBuf = std::env::current_exe().unwrap();
+            for _ in 0..3 { csv_path.pop(); }
+            for val in ["src", "board.csv"] { csv_path.push(val); }
+            paths.push(csv_path);
+        }
+
+        // Load the starting boards.
+        paths.iter().map(|path| {
+            let starting_board: ndarray::Array2<u8> = utils::load_board(path)
+                .unwrap_or_else(|e| exit_with(&format!("{}: {}", path.display(), e)));
+            Simulation::new(starting_board, &opts)
+        }).collect()
+    };
+
+    if opts.edit {
+        // Save over the board being edited, or to the current directory.
+        let save_path = opts.boards.first().cloned().unwrap_or_else(|| "board.csv".into());
+        let edited = edit(sims.remove(0).board.read().unwrap().clone(), &save_path);
+        sims.insert(0, Simulation::new(edited, &opts));
+    }
+
+    // Raw frames share stdout with nothing else, so report the frame size on stderr.
+    let mut stdout = std::io::stdout();
+    if opts.raw_frames {
+        eprintln!("raw frames: {}x{} rgb24", sims[0].cols * opts.scale, sims[0].rows * opts.scale);
+    }
+
+    // Per-generation timings in nanoseconds, for --profile.
+    let mut step_times: Vec<u128> = vec![];
+    let mut render_times: Vec<u128> = vec![];
+
+    // How long each cell has been alive, for --color-scheme.
+    let mut ages = AgeRenderer::new();
+
+    // The changes since the last frame, for --delta, and the terminal size it was drawn at.
+    let mut delta = DeltaRenderer::new();
+    let mut term_size: Option<(u16, u16)> = None;
+
+    // With --fps, each generation has a slot in the schedule, and is only drawn if it is on time.
+    let frame_budget = opts.fps.map(|fps| Duration::from_secs_f64(1.0 / fps as f64));
+    let mut next_frame = Instant::now();
+    let mut dropped_frames: usize = 0;
+
+    for iter in 0..opts.generations {
+        let mut render_start = Instant::now();
+        if opts.raw_frames {
+            // Stream the frame as fast as possible, without clearing the screen or sleeping.
+            let frame = utils::rgb_frame(&(sims[0].board.read().unwrap()), opts.scale);
+            if stdout.write_all(&frame).is_err() { break; }
+        }
+        else if frame_budget.is_some_and(|budget| Instant::now() > next_frame + budget) {
+            // More than a frame behind, so skip drawing this generation to catch up.
+            next_frame += frame_budget.unwrap();
+            dropped_frames += 1;
+        }
+        else {
+            if let Some(budget) = frame_budget {
+                // Sleep off whatever is left of the frame, then clear the screen.
+                sleep(next_frame.saturating_duration_since(Instant::now()));
+                next_frame += budget;
+                if !opts.delta { print!("{}[2J", 27 as char); }
+            }
+            else {
+                // Clear screen, sleep, and print the boards in a context block for the RwLocks.
+                if !opts.delta { print!("{}[2J", 27 as char); }
+                if opts.delay_ms > 0 { sleep(Duration::from_millis(opts.delay_ms)); }
+            }
+            // Don't count the frame delay as rendering time.
+            render_start = Instant::now();
+            if opts.delta {
+                // A resize can scroll or wrap what was drawn, so start over.
+                let size = terminal_size();
+                if size != term_size { delta.invalidate(); term_size = size; }
+                print!("{}", delta.render(&(sims[0].board.read().unwrap()), iter));
+            }
+            else if !opts.palette.colors.is_empty() {
+                // Track ages only when they are shown.
+                ages.update(&(sims[0].board.read().unwrap()));
+                println!("Frame {}", iter);
+                print!("{}", ages.render_with(opts.palette));
+            }
+            else if let ([sim], Some(every)) = (sims.as_slice(), opts.grid_labels) {
+                utils::print_board_with_labels(&(sim.board.read().unwrap()), &sim.rows, &sim.cols, &iter, &every);
+            }
+            else if let [sim] = sims.as_slice() {
+                utils::print_board(&(sim.board.read().unwrap()), &sim.rows, &sim.cols, &iter);
+            }
+            else {
+                let guards: Vec<_> = sims.iter().map(|sim| sim.board.read().unwrap()).collect();
+                let boards: Vec<&ndarray::Array2<u8>> = guards.iter().map(|guard| &**guard).collect();
+                println!("Frame {}", iter);
+                print!("{}", utils::render_side_by_side(&boards, "| "));
+            }
+            // Make sure the terminal keeps up, even without a delay between frames.
+            stdout.flush().unwrap();
+        }
+
+        let render_time = render_start.elapsed().as_nanos();
 
+        // Each board steps independently.
+        let step_start = Instant::now();
+        for sim in sims.iter_mut() { sim.step(&opts.config); }
+        let step_time = step_start.elapsed().as_nanos();
+
+        if opts.profile {
+            eprintln!("generation {}: step {} ns, render {} ns", iter, step_time, render_time);
+            step_times.push(step_time);
+            render_times.push(render_time);
+        }
+
+        let num_sims = sims.len();
+        for (i, sim) in sims.iter_mut().enumerate() {
+            if let Some(period) = sim.detect() {
+                // Report on stderr so raw frames on stdout stay clean.
+                let name = if num_sims > 1 { format!("Board {}: ", i + 1) } else { String::new() };
+                if period == 1 { eprintln!("{}Stable after generation {}", name, iter + 1); }
+                else { eprintln!("{}Oscillating with period {} after generation {}", name, period, iter + 1); }
+            }
+        }
+        if opts.stop_on_stable & sims.iter().all(|sim| sim.period.is_some()) { break; }
+
+        if let Some(target) = opts.stop_at_population {
+            let below = opts.stop_below.unwrap_or(false);
+            let crossed = sims.iter().all(|sim| {
+                let pop = utils::population(&(sim.board.read().unwrap()));
+                if below { pop <= target } else { pop >= target }
+            });
+            if crossed {
+                let direction = if below { "at or below" } else { "at or above" };
+                eprintln!("Population {} {} after generation {}", direction, target, iter + 1);
+                break;
+            }
+        }
+    }
+
+    if opts.profile {
+        print_timing_summary("step", &step_times);
+        print_timing_summary("render", &render_times);
+        if opts.fps.is_some() { eprintln!("dropped {} frames", dropped_frames); }
+    }
+}
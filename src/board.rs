@@ -0,0 +1,170 @@
+use std::fmt;
+
+/// A cell was set outside of the board.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutOfBounds {
+    pub row: usize,
+    pub col: usize,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cell ({}, {}) is outside of a {}x{} board", self.row, self.col, self.rows, self.cols)
+    }
+}
+
+impl std::error::Error for OutOfBounds {}
+
+/// Construct a board cell-by-cell, starting from an all-dead board.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::board::BoardBuilder;
+///
+/// let blinker = BoardBuilder::new(3, 3).set(1, 0).set(1, 1).set(1, 2).build().unwrap();
+/// assert_eq!(blinker, array![[0, 0, 0],
+///                            [1, 1, 1],
+///                            [0, 0, 0]]);
+///
+/// let block = BoardBuilder::new(2, 2).set_many(&[(0, 0), (0, 1), (1, 0), (1, 1)]).build().unwrap();
+/// assert_eq!(block, array![[1, 1], [1, 1]]);
+///
+/// assert!(BoardBuilder::new(2, 2).set(2, 0).build().is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoardBuilder {
+    rows: usize,
+    cols: usize,
+    cells: Vec<(usize, usize)>,
+}
+
+impl BoardBuilder {
+    /// Start a builder for an all-dead board.
+    ///
+    /// # Arguments
+    /// `rows` - the number of rows.
+    /// `cols` - the number of columns.
+    pub fn new(rows: usize, cols: usize) -> BoardBuilder {
+        return BoardBuilder { rows, cols, cells: vec![] }
+    }
+
+    /// Turn on a single cell.
+    ///
+    /// # Arguments
+    /// `r` - the row of the cell.
+    /// `c` - the column of the cell.
+    pub fn set(mut self, r: usize, c: usize) -> BoardBuilder {
+        self.cells.push((r, c));
+        return self
+    }
+
+    /// Turn on every cell in a collection of positions.
+    ///
+    /// # Arguments
+    /// `pos_arr` - the positions to turn on.
+    pub fn set_many(mut self, pos_arr: &[(usize, usize)]) -> BoardBuilder {
+        self.cells.extend_from_slice(pos_arr);
+        return self
+    }
+
+    /// Build the board.
+    ///
+    /// # Returns
+    /// The board, or the first cell that does not fit on it.
+    pub fn build(self) -> Result<ndarray::Array2<u8>, OutOfBounds> {
+        let mut brd: ndarray::Array2<u8> = ndarray::Array2::zeros((self.rows, self.cols));
+        for &(r, c) in &self.cells {
+            if (r >= self.rows) | (c >= self.cols) {
+                return Err(OutOfBounds { row: r, col: c, rows: self.rows, cols: self.cols });
+            }
+            brd[[r, c]] = 1;
+        }
+        return Ok(brd)
+    }
+}
+
+/// A board of cells, each either dead (0) or alive (1).
+///
+/// Displaying a board produces the same text as `utils::print_board`, without the `Frame N`
+/// header: one line per row with each cell followed by a space. Use `framed` to include the header.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::board::Board;
+///
+/// let board = Board::new(array![[0, 1], [1, 0]]);
+/// assert_eq!(board.to_string(), "0 1 \n1 0 \n");
+/// assert_eq!(format!("{}", board.framed(3)), "Frame 3\n0 1 \n1 0 \n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    cells: ndarray::Array2<u8>,
+}
+
+impl Board {
+    /// Wrap an array of cells.
+    pub fn new(cells: ndarray::Array2<u8>) -> Board {
+        return Board { cells }
+    }
+
+    /// The cells of the board.
+    pub fn cells(&self) -> &ndarray::Array2<u8> {
+        return &self.cells
+    }
+
+    /// Unwrap the array of cells.
+    pub fn into_cells(self) -> ndarray::Array2<u8> {
+        return self.cells
+    }
+
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        return self.cells.shape()[0]
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        return self.cells.shape()[1]
+    }
+
+    /// Display the board preceded by a `Frame N` header, as `utils::print_board` does.
+    ///
+    /// # Arguments
+    /// `frame_num` - the frame number in the header.
+    pub fn framed(&self, frame_num: usize) -> Framed<'_> {
+        return Framed { board: self, frame_num }
+    }
+}
+
+impl From<ndarray::Array2<u8>> for Board {
+    fn from(cells: ndarray::Array2<u8>) -> Board {
+        return Board::new(cells)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Write straight to the formatter rather than building a string per row.
+        for row in self.cells.genrows() {
+            for v in row { write!(f, "{} ", v)?; }
+            writeln!(f)?;
+        }
+        return Ok(())
+    }
+}
+
+/// A board displayed with a `Frame N` header, see `Board::framed`.
+#[derive(Debug, Clone, Copy)]
+pub struct Framed<'a> {
+    board: &'a Board,
+    frame_num: usize,
+}
+
+impl fmt::Display for Framed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Frame {}", self.frame_num)?;
+        return write!(f, "{}", self.board)
+    }
+}
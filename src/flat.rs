@@ -0,0 +1,93 @@
+//! Stepping over flat, caller-owned buffers, for targets without `std` or an allocator, such as a
+//! microcontroller driving an LED matrix. This module and the rules in `rule` are all that is
+//! left of the crate when it is built without the default `std` feature.
+//!
+//! A board of `rows` by `cols` is a slice of `rows * cols` cells in row-major order, 0 for dead and
+//! 1 for alive. Cells beyond the edges are dead, as in `utils::next_generation`.
+
+use crate::rule::Transition;
+
+/// Determine the sum of all the neighbors of a given cell.
+///
+/// This is separate from `utils::count_neighbors`, which reads through an ndarray view that may
+/// not be contiguous, such as a slice of a larger board, and so can't be handed to this function.
+///
+/// # Arguments
+/// `cells` - the board.
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `r` - the row of the cell.
+/// `c` - the column of the cell.
+///
+/// # Returns
+/// The sum of all neighbors of a particular cell.
+///
+/// ```
+/// use game_of_life::flat::count_neighbors;
+///
+/// let cells: [u8; 6] = [1, 1, 0,
+///                       0, 1, 1];
+/// assert_eq!(count_neighbors(&cells, 2, 3, 0, 0), 2);
+/// assert_eq!(count_neighbors(&cells, 2, 3, 1, 1), 3);
+/// assert_eq!(count_neighbors(&[1], 1, 1, 0, 0), 0);
+/// ```
+pub fn count_neighbors(cells: &[u8], rows: usize, cols: usize, r: usize, c: usize) -> u8 {
+    let mut count: u8 = 0;
+    for nr in r.saturating_sub(1)..(r + 2).min(rows) {
+        for nc in c.saturating_sub(1)..(c + 2).min(cols) {
+            if (nr, nc) != (r, c) { count += cells[nr * cols + nc]; }
+        }
+    }
+    return count
+}
+
+/// Advance a board by one generation into a second buffer.
+///
+/// # Arguments
+/// `cells` - the board.
+/// `next` - the buffer for the next generation, the same size as `cells`.
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `rule` - the rule deciding births and survivals.
+///
+/// # Panics
+/// If either buffer isn't `rows * cols` cells long.
+///
+/// ```
+/// use game_of_life::{flat::step, rule::Rule};
+///
+/// // Both generations live on the stack.
+/// let mut front: [u8; 25] = [0, 0, 0, 0, 0,
+///                            0, 0, 1, 0, 0,
+///                            0, 0, 1, 0, 0,
+///                            0, 0, 1, 0, 0,
+///                            0, 0, 0, 0, 0];
+/// let mut back = [0u8; 25];
+/// step(&front, &mut back, 5, 5, &Rule::conway());
+/// assert_eq!(back, [0, 0, 0, 0, 0,
+///                   0, 0, 0, 0, 0,
+///                   0, 1, 1, 1, 0,
+///                   0, 0, 0, 0, 0,
+///                   0, 0, 0, 0, 0]);
+/// step(&back, &mut front, 5, 5, &Rule::conway());
+/// assert_eq!(front[7], 1);
+///
+/// // The same as the ndarray stepper, with the `std` feature.
+/// #[cfg(feature = "std")]
+/// {
+///     let brd = game_of_life::utils::random_board(9, 13, 0.4, 198);
+///     let mut next = [0u8; 9 * 13];
+///     step(brd.as_slice().unwrap(), &mut next, 9, 13, &Rule::conway());
+///     assert_eq!(&next[..], game_of_life::utils::next_generation(&brd).as_slice().unwrap());
+/// }
+/// ```
+pub fn step<R: Transition>(cells: &[u8], next: &mut [u8], rows: usize, cols: usize, rule: &R) {
+    assert_eq!(cells.len(), rows * cols, "the board must have rows * cols cells");
+    assert_eq!(next.len(), rows * cols, "the next board must have rows * cols cells");
+    for r in 0..rows {
+        for c in 0..cols {
+            let count = count_neighbors(cells, rows, cols, r, c);
+            next[r * cols + c] = rule.next_state(cells[r * cols + c] == 1, count) as u8;
+        }
+    }
+}
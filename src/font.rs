@@ -0,0 +1,78 @@
+//! Bitmap fonts for writing text onto a board, see `utils::board_from_banner`.
+
+/// A fixed-width bitmap font. Each glyph is a list of rows from the top, with the leftmost pixel
+/// of a row in bit `width - 1` and the rightmost in bit 0.
+///
+/// ```
+/// use game_of_life::font::FONT_5X7;
+///
+/// assert_eq!(FONT_5X7.glyph('T'), Some(&[0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100][..]));
+/// assert_eq!(FONT_5X7.glyph('t'), FONT_5X7.glyph('T'));
+/// assert_eq!(FONT_5X7.glyph('~'), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitmapFont {
+    /// The width of every glyph in pixels, at most 8.
+    pub width: usize,
+    /// The height of every glyph in pixels.
+    pub height: usize,
+    pub glyphs: &'static [(char, &'static [u8])],
+}
+
+impl BitmapFont {
+    /// The rows of a character's glyph, with lowercase letters drawn as uppercase, or `None` if the
+    /// font doesn't have it.
+    pub fn glyph(&self, ch: char) -> Option<&'static [u8]> {
+        let ch = ch.to_ascii_uppercase();
+        return self.glyphs.iter().find(|(glyph, _)| *glyph == ch).map(|&(_, rows)| rows)
+    }
+}
+
+/// A 5x7 font of the uppercase letters, the digits, space and `! . - ?`.
+pub const FONT_5X7: BitmapFont = BitmapFont {
+    width: 5,
+    height: 7,
+    glyphs: &[
+        ('A', &[0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+        ('B', &[0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110]),
+        ('C', &[0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110]),
+        ('D', &[0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110]),
+        ('E', &[0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111]),
+        ('F', &[0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000]),
+        ('G', &[0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111]),
+        ('H', &[0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+        ('I', &[0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+        ('J', &[0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100]),
+        ('K', &[0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001]),
+        ('L', &[0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111]),
+        ('M', &[0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001]),
+        ('N', &[0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001]),
+        ('O', &[0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+        ('P', &[0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000]),
+        ('Q', &[0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101]),
+        ('R', &[0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001]),
+        ('S', &[0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110]),
+        ('T', &[0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+        ('U', &[0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+        ('V', &[0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100]),
+        ('W', &[0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010]),
+        ('X', &[0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001]),
+        ('Y', &[0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100]),
+        ('Z', &[0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111]),
+        ('0', &[0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110]),
+        ('1', &[0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+        ('2', &[0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111]),
+        ('3', &[0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110]),
+        ('4', &[0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010]),
+        ('5', &[0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110]),
+        ('6', &[0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110]),
+        ('7', &[0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000]),
+        ('8', &[0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110]),
+        ('9', &[0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100]),
+        (' ', &[0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+        ('!', &[0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100]),
+        ('.', &[0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100]),
+        ('-', &[0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000]),
+        ('?', &[0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100]),
+    ],
+};
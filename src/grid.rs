@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+
+use crate::rule::{Rule, Transition};
+
+/// A board storage backend, so algorithms can be written once and run against any of them.
+///
+/// Cells outside of the grid are dead.
+pub trait LifeGrid {
+    /// An all-dead grid.
+    fn empty(rows: usize, cols: usize) -> Self where Self: Sized;
+
+    /// The number of rows and columns.
+    fn dims(&self) -> (usize, usize);
+
+    /// The value of a cell, 0 for dead and 1 for alive.
+    fn get(&self, r: usize, c: usize) -> u8;
+
+    /// Set the value of a cell, 0 for dead and 1 for alive.
+    fn set(&mut self, r: usize, c: usize, v: u8);
+
+    /// The number of live neighbors of a cell.
+    fn live_neighbors(&self, r: usize, c: usize) -> u8 {
+        let (rows, cols) = self.dims();
+        let mut count: u8 = 0;
+        for nr in r.saturating_sub(1)..(r + 2).min(rows) {
+            for nc in c.saturating_sub(1)..(c + 2).min(cols) {
+                if (nr, nc) != (r, c) { count += self.get(nr, nc); }
+            }
+        }
+        return count
+    }
+}
+
+/// Advance any grid by one generation under Conway's rules.
+///
+/// # Arguments
+/// `grid` - the grid.
+///
+/// # Returns
+/// The next generation, in the same backend.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use game_of_life::grid::{step, BitBoard, LifeGrid, SparseBoard};
+///
+/// let dense = array![[0, 1, 0, 0],
+///                    [0, 0, 1, 0],
+///                    [1, 1, 1, 0],
+///                    [0, 0, 0, 0]];
+/// let mut bits = BitBoard::empty(4, 4);
+/// let mut sparse = SparseBoard::empty(4, 4);
+/// for ((r, c), &v) in dense.indexed_iter() { bits.set(r, c, v); sparse.set(r, c, v); }
+///
+/// let dense_next: Array2<u8> = step(&dense);
+/// let bits_next = step(&bits);
+/// let sparse_next = step(&sparse);
+/// for ((r, c), &v) in dense_next.indexed_iter() {
+///     assert_eq!(bits_next.get(r, c), v);
+///     assert_eq!(sparse_next.get(r, c), v);
+/// }
+/// assert_eq!(dense_next, game_of_life::utils::next_generation(&dense));
+/// ```
+pub fn step<G: LifeGrid>(grid: &G) -> G {
+    return step_with_rule(grid, &Rule::conway())
+}
+
+/// Advance any grid by one generation under an arbitrary rule, such as a `Rule` or a `RangeRule`.
+///
+/// # Arguments
+/// `grid` - the grid.
+/// `rule` - the rule deciding births and survivals.
+///
+/// # Returns
+/// The next generation, in the same backend.
+///
+/// ```
+/// use game_of_life::{grid::{step_with_rule, BitBoard, SparseBoard},
+///                    rule::{RangeRule, Rule},
+///                    utils::{next_generation_with_rule, random_board}};
+///
+/// let soup = random_board(10, 12, 0.4, 137);
+/// let highlife = Rule::new(&[3, 6], &[2, 3]);
+/// let expected = next_generation_with_rule(&soup, &highlife);
+/// assert_eq!(step_with_rule(&soup, &highlife), expected);
+/// assert_eq!(ndarray::Array2::from(&step_with_rule(&BitBoard::from(&soup), &highlife)), expected);
+/// assert_eq!(ndarray::Array2::from(&step_with_rule(&SparseBoard::from(&soup), &highlife)), expected);
+///
+/// let lenient = RangeRule { survive_min: 1, survive_max: 4, birth_min: 3, birth_max: 4 };
+/// assert_eq!(step_with_rule(&soup, &lenient), next_generation_with_rule(&soup, &lenient));
+/// ```
+pub fn step_with_rule<G: LifeGrid, R: Transition>(grid: &G, rule: &R) -> G {
+    let (rows, cols) = grid.dims();
+    let mut next = G::empty(rows, cols);
+    for r in 0..rows {
+        for c in 0..cols {
+            if rule.next_state(grid.get(r, c) == 1, grid.live_neighbors(r, c)) { next.set(r, c, 1); }
+        }
+    }
+    return next
+}
+
+/// Copy a grid into another backend.
+///
+/// # Arguments
+/// `grid` - the grid.
+///
+/// # Returns
+/// A grid of the same size with the same live cells. The `From` conversions between backends
+/// are built on this.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use game_of_life::grid::{BitBoard, LifeGrid, SparseBoard};
+///
+/// let dense = array![[0, 1, 0],
+///                    [0, 0, 1],
+///                    [1, 1, 1]];
+/// let bits = BitBoard::from(&dense);
+/// let sparse = SparseBoard::from(&dense);
+/// assert_eq!(sparse.live().len(), 5);
+///
+/// assert_eq!(Array2::from(&bits), dense);
+/// assert_eq!(Array2::from(&sparse), dense);
+/// assert_eq!(Array2::from(&SparseBoard::from(&bits)), dense);
+/// assert_eq!(Array2::from(&BitBoard::from(&sparse)), dense);
+/// assert_eq!(SparseBoard::from(&BitBoard::from(&sparse)), sparse);
+/// assert_eq!(BitBoard::from(&SparseBoard::from(&bits)), bits);
+/// ```
+pub fn convert<A: LifeGrid, B: LifeGrid>(grid: &A) -> B {
+    let (rows, cols) = grid.dims();
+    let mut out = B::empty(rows, cols);
+    for r in 0..rows {
+        for c in 0..cols {
+            let v = grid.get(r, c);
+            if v != 0 { out.set(r, c, v); }
+        }
+    }
+    return out
+}
+
+impl LifeGrid for ndarray::Array2<u8> {
+    fn empty(rows: usize, cols: usize) -> ndarray::Array2<u8> {
+        return ndarray::Array2::zeros((rows, cols))
+    }
+
+    fn dims(&self) -> (usize, usize) {
+        return self.dim()
+    }
+
+    fn get(&self, r: usize, c: usize) -> u8 {
+        return self[[r, c]]
+    }
+
+    fn set(&mut self, r: usize, c: usize, v: u8) {
+        self[[r, c]] = v;
+    }
+}
+
+/// A board packed one bit per cell, in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitBoard {
+    rows: usize,
+    cols: usize,
+    words: Vec<u64>,
+}
+
+impl BitBoard {
+    /// The word and bit holding a cell.
+    fn locate(&self, r: usize, c: usize) -> (usize, u64) {
+        assert!((r < self.rows) & (c < self.cols), "cell ({}, {}) is outside of the board", r, c);
+        let idx = r * self.cols + c;
+        return (idx / 64, 1 << (idx % 64))
+    }
+}
+
+impl LifeGrid for BitBoard {
+    fn empty(rows: usize, cols: usize) -> BitBoard {
+        return BitBoard { rows, cols, words: vec![0; (rows * cols).div_ceil(64)] }
+    }
+
+    fn dims(&self) -> (usize, usize) {
+        return (self.rows, self.cols)
+    }
+
+    fn get(&self, r: usize, c: usize) -> u8 {
+        let (word, bit) = self.locate(r, c);
+        return (self.words[word] & bit != 0) as u8
+    }
+
+    fn set(&mut self, r: usize, c: usize, v: u8) {
+        let (word, bit) = self.locate(r, c);
+        if v == 1 { self.words[word] |= bit; } else { self.words[word] &= !bit; }
+    }
+}
+
+/// A board storing only the coordinates of its live cells, for large mostly-dead boards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseBoard {
+    rows: usize,
+    cols: usize,
+    live: HashSet<(usize, usize)>,
+}
+
+impl SparseBoard {
+    /// The coordinates of the live cells, in no particular order.
+    pub fn live(&self) -> &HashSet<(usize, usize)> {
+        return &self.live
+    }
+}
+
+impl LifeGrid for SparseBoard {
+    fn empty(rows: usize, cols: usize) -> SparseBoard {
+        return SparseBoard { rows, cols, live: HashSet::new() }
+    }
+
+    fn dims(&self) -> (usize, usize) {
+        return (self.rows, self.cols)
+    }
+
+    fn get(&self, r: usize, c: usize) -> u8 {
+        return self.live.contains(&(r, c)) as u8
+    }
+
+    fn set(&mut self, r: usize, c: usize, v: u8) {
+        assert!((r < self.rows) & (c < self.cols), "cell ({}, {}) is outside of the board", r, c);
+        if v == 1 { self.live.insert((r, c)); } else { self.live.remove(&(r, c)); }
+    }
+}
+
+impl From<&ndarray::Array2<u8>> for BitBoard {
+    fn from(grid: &ndarray::Array2<u8>) -> BitBoard {
+        return convert(grid)
+    }
+}
+
+impl From<&BitBoard> for ndarray::Array2<u8> {
+    fn from(grid: &BitBoard) -> ndarray::Array2<u8> {
+        return convert(grid)
+    }
+}
+
+impl From<&ndarray::Array2<u8>> for SparseBoard {
+    fn from(grid: &ndarray::Array2<u8>) -> SparseBoard {
+        return convert(grid)
+    }
+}
+
+impl From<&SparseBoard> for ndarray::Array2<u8> {
+    fn from(grid: &SparseBoard) -> ndarray::Array2<u8> {
+        return convert(grid)
+    }
+}
+
+impl From<&BitBoard> for SparseBoard {
+    fn from(grid: &BitBoard) -> SparseBoard {
+        return convert(grid)
+    }
+}
+
+impl From<&SparseBoard> for BitBoard {
+    fn from(grid: &SparseBoard) -> BitBoard {
+        return convert(grid)
+    }
+}
@@ -1 +1,27 @@
-pub mod utils;
\ No newline at end of file
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::needless_return)]
+
+#[cfg(feature = "std")]
+pub mod board;
+pub mod flat;
+#[cfg(feature = "std")]
+pub mod font;
+#[cfg(feature = "std")]
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod macrocell;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "std")]
+pub mod pattern;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod rle;
+pub mod rule;
+#[cfg(feature = "simd")]
+pub mod simd;
+#[cfg(feature = "std")]
+pub mod simulation;
+#[cfg(feature = "std")]
+pub mod utils;
@@ -0,0 +1,236 @@
+//! Golly's `.mc` macrocell format, which stores a board as a quadtree of deduplicated nodes.
+//!
+//! A file starts with a `[M2]` header, then lists the nodes bottom-up, numbered from 1. Each
+//! 8x8 leaf is a line of rows, with `.` for dead cells, `*` for live cells and each row ended by a
+//! `$`; trailing dead cells and rows are left out. Every larger node is a line
+//! `level nw ne sw se` giving its level (a node of level `n` is `2^n` cells across) and the
+//! numbers of its four quadrants, with 0 for an empty quadrant. The last node is the root.
+
+use std::{collections::HashMap, fmt};
+
+/// A macrocell file could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacrocellError {
+    /// The line of the problem, counting from 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for MacrocellError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "macrocell line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for MacrocellError {}
+
+/// The level of the 8x8 leaves.
+static LEAF_LEVEL: u32 = 3;
+
+/// The largest level read from a file, for a board of 16384x16384 cells. The whole board is
+/// allocated when the file is read, so larger roots are refused rather than tried.
+pub static MAX_LEVEL: u32 = 14;
+
+/// Serializes a board into quadtree nodes, sharing identical nodes.
+struct Writer<'a> {
+    brd: &'a ndarray::Array2<u8>,
+    lines: Vec<String>,
+    ids: HashMap<String, usize>,
+}
+
+impl Writer<'_> {
+    /// The number of a node, adding it (and its quadrants) if it hasn't been seen before.
+    fn node(&mut self, r0: usize, c0: usize, level: u32) -> usize {
+        let line = if level == LEAF_LEVEL { self.leaf(r0, c0) } else {
+            let half = 1 << (level - 1);
+            let nw = self.node(r0, c0, level - 1);
+            let ne = self.node(r0, c0 + half, level - 1);
+            let sw = self.node(r0 + half, c0, level - 1);
+            let se = self.node(r0 + half, c0 + half, level - 1);
+            if (nw, ne, sw, se) == (0, 0, 0, 0) { return 0; }
+            format!("{} {} {} {} {}", level, nw, ne, sw, se)
+        };
+        if line.is_empty() { return 0; }
+        if let Some(&id) = self.ids.get(&line) { return id; }
+        self.lines.push(line.clone());
+        self.ids.insert(line, self.lines.len());
+        return self.lines.len()
+    }
+
+    /// The line for the 8x8 leaf at (r0, c0), empty if the leaf is all dead.
+    fn leaf(&self, r0: usize, c0: usize) -> String {
+        let cell = |r: usize, c: usize| self.brd.get([r, c]).copied().unwrap_or(0) == 1;
+        let mut line = String::new();
+        let mut pending_rows = 0;
+        for r in r0..r0 + 8 {
+            let row: String = (c0..c0 + 8).map(|c| if cell(r, c) { '*' } else { '.' }).collect();
+            let row = row.trim_end_matches('.');
+            if row.is_empty() { pending_rows += 1; continue; }
+            for _ in 0..pending_rows { line.push('$'); }
+            pending_rows = 0;
+            line.push_str(row);
+            line.push('$');
+        }
+        return line
+    }
+}
+
+/// Write a board in macrocell format. The board is placed at the top left of the smallest
+/// square quadtree that holds it.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The contents of a `.mc` file.
+///
+/// ```
+/// use ndarray::{array, s};
+/// use game_of_life::macrocell::{from_macrocell, to_macrocell};
+///
+/// let glider = array![[0, 1, 0],
+///                     [0, 0, 1],
+///                     [1, 1, 1]];
+/// let mc = to_macrocell(&glider);
+/// assert_eq!(mc, "[M2] (game_of_life)\n#R B3/S23\n.*$..*$***$\n");
+///
+/// let back = from_macrocell(&mc).unwrap();
+/// assert_eq!(back.dim(), (8, 8));
+/// assert_eq!(back.slice(s![..3, ..3]), glider);
+///
+/// let mut big = ndarray::Array2::<u8>::zeros((20, 11));
+/// big[[0, 0]] = 1;
+/// big[[19, 10]] = 1;
+/// big[[12, 3]] = 1;
+/// let back = from_macrocell(&to_macrocell(&big)).unwrap();
+/// assert_eq!(back.dim(), (32, 32));
+/// assert_eq!(back.slice(s![..20, ..11]), big);
+/// assert_eq!(game_of_life::utils::population(&back), 3);
+/// ```
+pub fn to_macrocell(brd: &ndarray::Array2<u8>) -> String {
+    let (rows, cols) = brd.dim();
+    let mut level = LEAF_LEVEL;
+    while (1 << level) < rows.max(cols) { level += 1; }
+
+    let mut writer = Writer { brd, lines: vec![], ids: HashMap::new() };
+    // An empty board still needs a root.
+    if writer.node(0, 0, level) == 0 { writer.lines.push("$".to_string()); }
+
+    let mut out = String::from("[M2] (game_of_life)\n#R B3/S23\n");
+    for line in &writer.lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+    return out
+}
+
+/// Write a board to a `.mc` file, see `to_macrocell`.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `path` - the file to write.
+pub fn save_macrocell<P: AsRef<std::path::Path>>(brd: &ndarray::Array2<u8>, path: P) -> std::io::Result<()> {
+    return std::fs::write(path, to_macrocell(brd))
+}
+
+/// A parsed quadtree node.
+enum Node {
+    Leaf(Vec<(usize, usize)>),
+    Inner(u32, [usize; 4]),
+}
+
+/// Draw a node onto a board with its top left corner at (r0, c0).
+fn draw(nodes: &[Node], id: usize, r0: usize, c0: usize, brd: &mut ndarray::Array2<u8>) {
+    if id == 0 { return; }
+    match &nodes[id - 1] {
+        Node::Leaf(cells) => for &(r, c) in cells { brd[[r0 + r, c0 + c]] = 1; },
+        Node::Inner(level, children) => {
+            let half = 1 << (level - 1);
+            let offsets = [(0, 0), (0, half), (half, 0), (half, half)];
+            for (&child, &(dr, dc)) in children.iter().zip(offsets.iter()) {
+                draw(nodes, child, r0 + dr, c0 + dc, brd);
+            }
+        }
+    }
+}
+
+/// Read a board in macrocell format.
+///
+/// # Arguments
+/// `text` - the contents of a `.mc` file.
+///
+/// # Returns
+/// A square board as large as the root node, or an error if the text is not a valid two-state
+/// macrocell file or has a node above `MAX_LEVEL`.
+///
+/// ```
+/// use game_of_life::macrocell::from_macrocell;
+///
+/// assert!(from_macrocell("[M2]\n4 1 0 0 0\n").is_err());
+/// assert!(from_macrocell("[M2]\n*$\n5 1 0 0 0\n").is_err());
+/// assert_eq!(from_macrocell("[M2]\n*$\n4 0 0 0 1\n").unwrap()[[8, 8]], 1);
+/// assert!(from_macrocell("[M2]\n.*x$\n").is_err());
+///
+/// // Cells outside the leaf, and nodes too large to allocate.
+/// assert!(from_macrocell("[M2]\n$$$$$$$$*\n").is_err());
+/// assert!(from_macrocell("[M2]\n........*\n").is_err());
+/// assert!(from_macrocell("[M2]\n64 0 0 0 0\n").is_err());
+/// assert!(from_macrocell("[M2]\n40 0 0 0 0\n").is_err());
+/// assert!(from_macrocell("[M2]\n4294967300 0 0 0 0\n").is_err());
+/// assert_eq!(from_macrocell("[M2]\n.......*$$$$$$$\n").unwrap()[[0, 7]], 1);
+/// ```
+pub fn from_macrocell(text: &str) -> Result<ndarray::Array2<u8>, MacrocellError> {
+    let mut nodes: Vec<Node> = vec![];
+    for (idx, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        let err = |message: &str| MacrocellError { line: idx + 1, message: message.to_string() };
+        if line.is_empty() | line.starts_with('[') | line.starts_with('#') { continue; }
+
+        if line.starts_with(|ch: char| ch.is_ascii_digit()) {
+            let fields: Vec<usize> = line.split_whitespace().map(|f| f.parse().map_err(|_| err("invalid number")))
+                                         .collect::<Result<_, _>>()?;
+            if fields.len() != 5 { return Err(err("expected a level and four quadrants")); }
+            if fields[0] > MAX_LEVEL as usize { return Err(err("node level is too large")); }
+            let level = fields[0] as u32;
+            if level <= LEAF_LEVEL { return Err(err("node level is too small")); }
+            let children = [fields[1], fields[2], fields[3], fields[4]];
+            for &child in &children {
+                let child_level = match child.checked_sub(1).and_then(|i| nodes.get(i)) {
+                    _ if child == 0 => continue,
+                    Some(Node::Leaf(_)) => LEAF_LEVEL,
+                    Some(Node::Inner(l, _)) => *l,
+                    None => return Err(err("quadrant refers to a later node")),
+                };
+                if child_level != level - 1 { return Err(err("quadrant has the wrong level")); }
+            }
+            nodes.push(Node::Inner(level, children));
+        }
+        else {
+            let mut cells: Vec<(usize, usize)> = vec![];
+            let (mut r, mut c) = (0, 0);
+            for ch in line.chars() {
+                match ch {
+                    '.' => c += 1,
+                    '*' => {
+                        if (r >= 8) | (c >= 8) { return Err(err("leaf is larger than 8x8")); }
+                        cells.push((r, c));
+                        c += 1;
+                    }
+                    '$' => { r += 1; c = 0; }
+                    _ => return Err(err("unexpected character in leaf")),
+                }
+                if (r > 8) | (c > 8) { return Err(err("leaf is larger than 8x8")); }
+            }
+            nodes.push(Node::Leaf(cells));
+        }
+    }
+
+    let level = match nodes.last() {
+        Some(Node::Leaf(_)) => LEAF_LEVEL,
+        Some(Node::Inner(level, _)) => *level,
+        None => return Err(MacrocellError { line: 0, message: "no nodes".to_string() }),
+    };
+    let mut brd: ndarray::Array2<u8> = ndarray::Array2::zeros((1 << level, 1 << level));
+    draw(&nodes, nodes.len(), 0, 0, &mut brd);
+    return Ok(brd)
+}
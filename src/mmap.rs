@@ -0,0 +1,191 @@
+//! Boards kept in memory-mapped files, for dense boards too large to fit in memory. The operating
+//! system pages the cells in and out of the files as they are stepped.
+//!
+//! A board file holds one byte per cell, 0 or 1, in row-major order with no header, so a board
+//! of `rows` by `cols` is exactly `rows * cols` bytes long. The shape is not stored and has to be
+//! given when the file is opened. Each byte is a whole cell, so the mapping needs no alignment
+//! beyond the page alignment the operating system gives it.
+//!
+//! Mapping a file is `unsafe`: the cells are borrowed straight from the file, so nothing else may
+//! change, truncate or map the file while it is mapped here. Requires the `mmap` feature.
+
+use std::{fs::{File, OpenOptions}, io, path::{Path, PathBuf}};
+
+use crate::utils::count_neighbors;
+use crate::rule::{Rule, Transition};
+
+/// A board stored in a shared memory-mapped file, so changes to the cells are changes to the file.
+pub struct MmapBoard {
+    map: memmap2::MmapMut,
+    /// The file, to stop a simulation from using it twice.
+    path: PathBuf,
+    rows: usize,
+    cols: usize,
+}
+
+impl MmapBoard {
+    /// Create a board file of all dead cells, replacing any file already there.
+    ///
+    /// # Arguments
+    /// `path` - the file to create.
+    /// `rows` - the number of rows.
+    /// `cols` - the number of columns.
+    ///
+    /// # Safety
+    /// While the board is alive, the file must not be changed, truncated or mapped again, by this
+    /// process or any other.
+    pub unsafe fn create<P: AsRef<Path>>(path: P, rows: usize, cols: usize) -> io::Result<MmapBoard> {
+        let len = board_len(rows, cols)?;
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path.as_ref())?;
+        file.set_len(len as u64)?;
+        return MmapBoard::map(&file, path.as_ref(), rows, cols)
+    }
+
+    /// Create a board file holding a copy of a board.
+    ///
+    /// # Arguments
+    /// `brd` - the board.
+    /// `path` - the file to create.
+    ///
+    /// # Safety
+    /// The same as `create`.
+    pub unsafe fn from_board<P: AsRef<Path>>(brd: &ndarray::Array2<u8>, path: P) -> io::Result<MmapBoard> {
+        let mut board = MmapBoard::create(path, brd.shape()[0], brd.shape()[1])?;
+        board.view_mut().assign(brd);
+        return Ok(board)
+    }
+
+    /// Map an existing board file.
+    ///
+    /// # Arguments
+    /// `path` - the file, which must be exactly `rows * cols` bytes long.
+    /// `rows` - the number of rows.
+    /// `cols` - the number of columns.
+    ///
+    /// # Safety
+    /// The same as `create`.
+    pub unsafe fn open<P: AsRef<Path>>(path: P, rows: usize, cols: usize) -> io::Result<MmapBoard> {
+        let len = board_len(rows, cols)?;
+        let file = OpenOptions::new().read(true).write(true).open(path.as_ref())?;
+        if file.metadata()?.len() != len as u64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("board file is not {}x{} cells", rows, cols)));
+        }
+        return MmapBoard::map(&file, path.as_ref(), rows, cols)
+    }
+
+    unsafe fn map(file: &File, path: &Path, rows: usize, cols: usize) -> io::Result<MmapBoard> {
+        let map = memmap2::MmapOptions::new().len(rows * cols).map_mut(file)?;
+        return Ok(MmapBoard { map, path: path.canonicalize()?, rows, cols })
+    }
+
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        return self.rows
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        return self.cols
+    }
+
+    /// The cells.
+    pub fn view(&self) -> ndarray::ArrayView2<'_, u8> {
+        return ndarray::ArrayView2::from_shape((self.rows, self.cols), &self.map[..]).unwrap()
+    }
+
+    /// The cells, for changing.
+    pub fn view_mut(&mut self) -> ndarray::ArrayViewMut2<'_, u8> {
+        return ndarray::ArrayViewMut2::from_shape((self.rows, self.cols), &mut self.map[..]).unwrap()
+    }
+
+    /// Write changed cells out to the file now, rather than whenever the operating system chooses.
+    pub fn flush(&self) -> io::Result<()> {
+        return self.map.flush()
+    }
+}
+
+/// The length of a board file, which can't be empty since mapping zero bytes is an error.
+fn board_len(rows: usize, cols: usize) -> io::Result<usize> {
+    return match rows.checked_mul(cols) {
+        Some(0) => Err(io::Error::new(io::ErrorKind::InvalidInput, "board has no cells")),
+        Some(len) => Ok(len),
+        None => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("a {}x{} board is too large", rows, cols))),
+    }
+}
+
+/// Steps a memory-mapped board, writing each generation into a second board file of the same
+/// shape and then swapping the two, so neither is ever held in memory as a whole.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::{mmap::{MmapBoard, MmapSimulation}, utils::{next_generation, random_board}};
+///
+/// let dir = std::env::temp_dir();
+/// let mut brd: Array2<u8> = random_board(20, 30, 0.4, 7);
+/// // Neither file is used by anything else while it is mapped.
+/// let board = unsafe { MmapBoard::from_board(&brd, dir.join("game_of_life_mmap_a.bin")).unwrap() };
+/// let mut sim = unsafe { MmapSimulation::new(board, dir.join("game_of_life_mmap_b.bin")).unwrap() };
+/// for _ in 0..10 {
+///     sim.step();
+///     brd = next_generation(&brd);
+///     assert_eq!(sim.board().view(), brd);
+/// }
+/// sim.board().flush().unwrap();
+///
+/// // The board can't be its own scratch file.
+/// let path = dir.join("game_of_life_mmap_c.bin");
+/// let board = unsafe { MmapBoard::from_board(&brd, &path).unwrap() };
+/// assert!(unsafe { MmapSimulation::new(board, &path) }.is_err());
+/// ```
+pub struct MmapSimulation {
+    current: MmapBoard,
+    next: MmapBoard,
+    rule: Rule,
+}
+
+impl MmapSimulation {
+    /// Start stepping a board with Conway's game of life.
+    ///
+    /// # Arguments
+    /// `board` - the starting board.
+    /// `scratch` - the file for the other generation, created or replaced. It can't be the file of
+    /// `board`.
+    ///
+    /// # Safety
+    /// The same as `MmapBoard::create`, for `scratch`.
+    pub unsafe fn new<P: AsRef<Path>>(board: MmapBoard, scratch: P) -> io::Result<MmapSimulation> {
+        // A scratch file that doesn't exist yet can't be the board's.
+        if scratch.as_ref().canonicalize().ok().as_ref() == Some(&board.path) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "the scratch file is the board's own file"));
+        }
+        let next = MmapBoard::create(scratch, board.rows(), board.cols())?;
+        return Ok(MmapSimulation { current: board, next, rule: Rule::conway() })
+    }
+
+    /// Step with a different rule.
+    ///
+    /// # Arguments
+    /// `rule` - the rule.
+    pub fn with_rule(mut self, rule: Rule) -> MmapSimulation {
+        self.rule = rule;
+        return self
+    }
+
+    /// The current generation. After an odd number of steps, this is the scratch file.
+    pub fn board(&self) -> &MmapBoard {
+        return &self.current
+    }
+
+    /// Advance the board by one generation, with the edges of the board dead, as
+    /// `utils::next_generation` does.
+    pub fn step(&mut self) {
+        let (rows, cols) = (self.current.rows(), self.current.cols());
+        let current = self.current.view();
+        let mut next = self.next.view_mut();
+        for ((r, c), v) in next.indexed_iter_mut() {
+            let count = count_neighbors(&current, &rows, &cols, &r, &c);
+            *v = self.rule.next_state(current[[r, c]] == 1, count) as u8;
+        }
+        std::mem::swap(&mut self.current, &mut self.next);
+    }
+}
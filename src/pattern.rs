@@ -0,0 +1,70 @@
+/// A well-known pattern, written in the inline format of `utils::board_from_string`.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::pattern::{Pattern, PATTERNS};
+///
+/// let glider = Pattern::named("glider").unwrap();
+/// assert_eq!(glider.board(), array![[0, 1, 0], [0, 0, 1], [1, 1, 1]]);
+/// assert_eq!(Pattern::named("pulsar").unwrap().board().dim(), (13, 13));
+/// assert!(Pattern::named("teapot").is_none());
+///
+/// for pattern in PATTERNS { assert!(game_of_life::utils::population(&pattern.board()) > 0); }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pattern {
+    pub name: &'static str,
+    pub cells: &'static str,
+}
+
+impl Pattern {
+    /// Look up one of `PATTERNS` by name.
+    pub fn named(name: &str) -> Option<&'static Pattern> {
+        return PATTERNS.iter().find(|pattern| pattern.name == name)
+    }
+
+    /// The pattern on a board just large enough to hold it.
+    pub fn board(&self) -> ndarray::Array2<u8> {
+        return crate::utils::board_from_string(self.cells).expect("built-in patterns are valid")
+    }
+}
+
+/// The smallest still life.
+pub const BLOCK: Pattern = Pattern { name: "block", cells: "11/11" };
+
+/// A still life of six cells.
+pub const BEEHIVE: Pattern = Pattern { name: "beehive", cells: "0110/1001/0110" };
+
+/// A still life of seven cells.
+pub const LOAF: Pattern = Pattern { name: "loaf", cells: "0110/1001/0101/0010" };
+
+/// A still life of five cells.
+pub const BOAT: Pattern = Pattern { name: "boat", cells: "110/101/010" };
+
+/// The smallest oscillator, with period 2.
+pub const BLINKER: Pattern = Pattern { name: "blinker", cells: "111" };
+
+/// A period 2 oscillator.
+pub const TOAD: Pattern = Pattern { name: "toad", cells: "0111/1110" };
+
+/// A period 2 oscillator of two blocks touching at a corner.
+pub const BEACON: Pattern = Pattern { name: "beacon", cells: "1100/1100/0011/0011" };
+
+/// A period 3 oscillator.
+pub const PULSAR: Pattern = Pattern {
+    name: "pulsar",
+    cells: "0011100011100/0000000000000/1000010100001/1000010100001/1000010100001/0011100011100/0000000000000/\
+            0011100011100/1000010100001/1000010100001/1000010100001/0000000000000/0011100011100",
+};
+
+/// The smallest spaceship, moving one cell diagonally every 4 generations.
+pub const GLIDER: Pattern = Pattern { name: "glider", cells: "010/001/111" };
+
+/// The lightweight spaceship, moving two cells left every 4 generations.
+pub const LWSS: Pattern = Pattern { name: "lwss", cells: "01001/10000/10001/11110" };
+
+/// A methuselah that takes 1103 generations to settle.
+pub const R_PENTOMINO: Pattern = Pattern { name: "r-pentomino", cells: "011/110/010" };
+
+/// Every pattern that can be looked up by name.
+pub static PATTERNS: &[Pattern] = &[BLOCK, BEEHIVE, LOAF, BOAT, BLINKER, TOAD, BEACON, PULSAR, GLIDER, LWSS, R_PENTOMINO];
@@ -0,0 +1,198 @@
+/// Render a board with each live cell colored by how many generations it has been alive, from
+/// bright for newborn cells to dim for old ones. The board itself stays binary; the ages are kept
+/// alongside it.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::{render::AgeRenderer, utils::next_generation};
+///
+/// let mut block = array![[0, 0, 0, 0],
+///                        [0, 1, 1, 0],
+///                        [0, 1, 1, 0],
+///                        [0, 0, 0, 0]];
+/// let mut renderer = AgeRenderer::new();
+/// for gen in 1..=3 {
+///     renderer.update(&block);
+///     assert_eq!(renderer.ages()[[1, 1]], gen);
+///     assert_eq!(renderer.ages()[[0, 0]], 0);
+///     block = next_generation(&block);
+/// }
+/// assert!(renderer.render().starts_with("0 0 0 0 \n0 \u{1b}[38;5;253m1\u{1b}[0m "));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AgeRenderer {
+    ages: ndarray::Array2<u32>,
+}
+
+impl AgeRenderer {
+    /// Create a renderer with no history.
+    pub fn new() -> AgeRenderer {
+        return AgeRenderer { ages: ndarray::Array2::zeros((0, 0)) }
+    }
+
+    /// The number of consecutive generations each cell has been alive, 0 for dead cells.
+    pub fn ages(&self) -> &ndarray::Array2<u32> {
+        return &self.ages
+    }
+
+    /// Record the next generation. Live cells get a year older and dead cells are reset to 0. A
+    /// board of a different shape starts the ages over.
+    ///
+    /// # Arguments
+    /// `brd` - the board.
+    pub fn update(&mut self, brd: &ndarray::Array2<u8>) {
+        if self.ages.dim() != brd.dim() { self.ages = ndarray::Array2::zeros(brd.dim()); }
+        ndarray::Zip::from(&mut self.ages).and(brd).apply(|age, &v| {
+            *age = if v == 1 { *age + 1 } else { 0 };
+        });
+    }
+
+    /// Render the last recorded board in the text format of `utils::print_board`, without the
+    /// header, coloring live cells with ANSI 256-color grays.
+    pub fn render(&self) -> String {
+        return self.render_with(&GRAY)
+    }
+
+    /// Render the last recorded board in the text format of `utils::print_board`, without the
+    /// header, coloring live cells by age from a palette.
+    ///
+    /// # Arguments
+    /// `palette` - the colors to use. With `mono`, this is the same text as `print_board`.
+    pub fn render_with(&self, palette: &Palette) -> String {
+        let mut out = String::new();
+        for row in self.ages.genrows() {
+            for &age in row {
+                match palette.color(age) {
+                    None if age == 0 => out.push_str("0 "),
+                    None => out.push_str("1 "),
+                    Some(code) => out.push_str(&format!("\x1b[38;5;{}m1\x1b[0m ", code)),
+                }
+            }
+            out.push('\n');
+        }
+        return out
+    }
+}
+
+/// A named set of ANSI 256-color codes for live cells, indexed by age: the first color is for newborn
+/// cells, and cells older than the palette use the last color. A palette with no colors renders in
+/// plain text.
+///
+/// ```
+/// use game_of_life::render::Palette;
+///
+/// let fire = Palette::named("fire").unwrap();
+/// assert_eq!(fire.color(0), None);
+/// assert_eq!(fire.color(1), Some(231));
+/// assert_eq!(fire.color(1000), Some(52));
+/// assert_eq!(Palette::named("mono").unwrap().color(1), None);
+/// assert!(Palette::named("plaid").is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub name: &'static str,
+    pub colors: &'static [u8],
+}
+
+impl Palette {
+    /// Look up one of `PALETTES` by name.
+    pub fn named(name: &str) -> Option<&'static Palette> {
+        return PALETTES.iter().find(|palette| palette.name == name)
+    }
+
+    /// The color code for a cell of a given age, or `None` for dead cells and plain palettes.
+    pub fn color(&self, age: u32) -> Option<u8> {
+        if (age == 0) | self.colors.is_empty() { return None; }
+        let idx = (age as usize - 1).min(self.colors.len() - 1);
+        return Some(self.colors[idx])
+    }
+}
+
+/// No color, the same as `utils::print_board`.
+pub const MONO: Palette = Palette { name: "mono", colors: &[] };
+
+/// The grayscale ramp from white (255) for a newborn cell, one step dimmer per generation, down to
+/// the darkest gray (232).
+pub const GRAY: Palette = Palette {
+    name: "gray",
+    colors: &[255, 254, 253, 252, 251, 250, 249, 248, 247, 246, 245, 244,
+              243, 242, 241, 240, 239, 238, 237, 236, 235, 234, 233, 232],
+};
+
+/// White-hot newborn cells cooling through yellow and orange to dark red.
+pub const FIRE: Palette = Palette { name: "fire", colors: &[231, 229, 226, 220, 214, 208, 202, 196, 160, 124, 88, 52] };
+
+/// Pale cyan newborn cells deepening to navy.
+pub const OCEAN: Palette = Palette { name: "ocean", colors: &[195, 159, 123, 87, 51, 45, 39, 33, 27, 21, 19, 17] };
+
+/// Bright green newborn cells fading to dark green.
+pub const MATRIX: Palette = Palette { name: "matrix", colors: &[156, 120, 84, 46, 40, 34, 28, 22] };
+
+/// Every palette that can be looked up by name.
+pub static PALETTES: &[Palette] = &[MONO, GRAY, FIRE, OCEAN, MATRIX];
+
+/// Render frames by redrawing only the cells that changed since the last frame, moving the cursor
+/// to each one with ANSI escapes. Over a slow terminal connection this is far less output than
+/// redrawing the board, and it doesn't flicker. The first frame, and any frame after a change of
+/// board shape or a call to `invalidate`, is drawn in full.
+///
+/// Frames are in the text format of `utils::print_board`, drawn from the top left corner of the
+/// screen.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::{render::DeltaRenderer, utils::next_generation};
+///
+/// let blinker = array![[0, 1, 0], [0, 1, 0], [0, 1, 0]];
+/// let mut renderer = DeltaRenderer::new();
+/// assert_eq!(renderer.render(&blinker, 0), "\u{1b}[2J\u{1b}[HFrame 0\n0 1 0 \n0 1 0 \n0 1 0 \n");
+///
+/// // The two ends of the blinker turn off and the two sides turn on.
+/// let frame = renderer.render(&next_generation(&blinker), 1);
+/// assert_eq!(frame, "\u{1b}[HFrame 1\u{1b}[K\
+///                    \u{1b}[2;3H0\u{1b}[3;1H1\u{1b}[3;5H1\u{1b}[4;3H0\
+///                    \u{1b}[5;1H");
+///
+/// renderer.invalidate();
+/// assert!(renderer.render(&blinker, 2).starts_with("\u{1b}[2J"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DeltaRenderer {
+    last: Option<ndarray::Array2<u8>>,
+}
+
+impl DeltaRenderer {
+    /// Create a renderer whose first frame is drawn in full.
+    pub fn new() -> DeltaRenderer {
+        return DeltaRenderer { last: None }
+    }
+
+    /// Draw the next frame in full, such as after the terminal is resized and its contents lost.
+    pub fn invalidate(&mut self) {
+        self.last = None;
+    }
+
+    /// Render a frame, and remember it for the next one.
+    ///
+    /// # Arguments
+    /// `brd` - the board.
+    /// `frame_num` - the frame number in the header.
+    pub fn render(&mut self, brd: &ndarray::Array2<u8>, frame_num: usize) -> String {
+        let (rows, cols) = brd.dim();
+        let out = match self.last.as_ref().and_then(|last| crate::utils::xor_board(last, brd).ok()) {
+            None => format!("\x1b[2J\x1b[H{}", crate::utils::format_board(brd, &rows, &cols, &frame_num)),
+            Some(changed) => {
+                // Rows and columns on the screen count from 1, below the header, two characters a cell.
+                let mut out = format!("\x1b[HFrame {}\x1b[K", frame_num);
+                for ((r, c), _) in changed.indexed_iter().filter(|(_, &v)| v == 1) {
+                    out.push_str(&format!("\x1b[{};{}H{}", r + 2, 2 * c + 1, brd[[r, c]]));
+                }
+                // Leave the cursor below the board.
+                out.push_str(&format!("\x1b[{};1H", rows + 2));
+                out
+            }
+        };
+        self.last = Some(brd.clone());
+        return out
+    }
+}
@@ -0,0 +1,248 @@
+//! The run length encoded `.rle` format used by Golly and most pattern collections.
+//!
+//! A file starts with a header line `x = <cols>, y = <rows>, rule = <rule>`, after any `#`
+//! comment lines. The cells follow row by row, as runs of a count and a tag, with the count left
+//! out for runs of one. `$` ends a row and `!` ends the pattern; dead cells at the end of a row,
+//! and empty rows at the end of the pattern, are left out.
+//!
+//! Two state patterns write `b` for dead cells and `o` for live cells. Patterns of Generations
+//! rules such as `B2/S/C3` write `.` for dead cells and `A` to `X`, then `pA` to `pX`, `qA` and so
+//! on, for states 1 upwards.
+
+use std::fmt;
+
+use crate::rule::GenerationsRule;
+
+/// An RLE file could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RleError {
+    /// The line of the problem, counting from 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rle line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RleError {}
+
+/// The longest line written, as recommended by the format.
+static MAX_LINE: usize = 70;
+
+/// The tag for a state, for patterns with more than 2 states.
+fn state_tag(state: u8) -> String {
+    if state == 0 { return ".".to_string(); }
+    let letter = (b'A' + (state - 1) % 24) as char;
+    return match (state - 1) / 24 {
+        0 => letter.to_string(),
+        prefix => format!("{}{}", (b'p' + prefix - 1) as char, letter),
+    }
+}
+
+/// Write a board in RLE format.
+///
+/// # Arguments
+/// `brd` - the board, with cells from 0 to `rule.states() - 1`.
+/// `rule` - the rule written in the header, which also decides how cells are written.
+///
+/// # Returns
+/// The contents of an `.rle` file.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::{rle::{from_rle, to_rle}, rule::{GenerationsRule, Rule}};
+///
+/// let glider = array![[0, 1, 0],
+///                     [0, 0, 1],
+///                     [1, 1, 1]];
+/// let life = GenerationsRule::from(Rule::conway());
+/// assert_eq!(to_rle(&glider, &life), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+/// assert_eq!(from_rle(&to_rle(&glider, &life)).unwrap(), (glider, life));
+///
+/// let brain: GenerationsRule = "B2/S/C3".parse().unwrap();
+/// let cells = array![[0, 1, 2, 0],
+///                    [0, 0, 0, 0],
+///                    [2, 2, 1, 1]];
+/// let rle = to_rle(&cells, &brain);
+/// assert_eq!(rle, "x = 4, y = 3, rule = B2/S/C3\n.AB2$2B2A!\n");
+/// assert_eq!(from_rle(&rle).unwrap(), (cells, brain));
+/// ```
+pub fn to_rle(brd: &ndarray::Array2<u8>, rule: &GenerationsRule) -> String {
+    let tag = |state: u8| if rule.states() > 2 { state_tag(state) }
+                          else if state == 0 { "b".to_string() } else { "o".to_string() };
+
+    // Collect the tokens, then wrap them into lines.
+    let mut tokens: Vec<String> = vec![];
+    let mut pending_rows: usize = 0;
+    for row in brd.genrows() {
+        let mut runs: Vec<(u8, usize)> = vec![];
+        for &v in row {
+            match runs.last_mut() {
+                Some((state, len)) if *state == v => *len += 1,
+                _ => runs.push((v, 1)),
+            }
+        }
+        if let Some(&(0, _)) = runs.last() { runs.pop(); }
+        if runs.is_empty() { pending_rows += 1; continue; }
+
+        if pending_rows > 0 {
+            tokens.push(if pending_rows > 1 { format!("{}$", pending_rows) } else { "$".to_string() });
+        }
+        for (state, len) in runs {
+            tokens.push(if len > 1 { format!("{}{}", len, tag(state)) } else { tag(state) });
+        }
+        pending_rows = 1;
+    }
+    tokens.push("!".to_string());
+
+    let mut out = format!("x = {}, y = {}, rule = {}\n", brd.shape()[1], brd.shape()[0], rule);
+    let mut line = String::new();
+    for token in tokens {
+        if line.len() + token.len() > MAX_LINE {
+            out.push_str(&line);
+            out.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    out.push_str(&line);
+    out.push('\n');
+    return out
+}
+
+/// The most cells `from_rle` allocates for a board, 16384x16384.
+pub static MAX_RLE_CELLS: usize = 1 << 28;
+
+/// Read a board in RLE format, of at most `MAX_RLE_CELLS` cells.
+///
+/// # Arguments
+/// `text` - the contents of an `.rle` file.
+///
+/// # Returns
+/// The board, as large as the header says, and its rule, which is Conway's game of life if the
+/// header doesn't give one. Or an error if the text is not a valid RLE file.
+///
+/// ```
+/// use game_of_life::{rle::from_rle, rule::GenerationsRule};
+///
+/// let (brd, rule) = from_rle("#N Blinker\nx = 3, y = 1\n3o!").unwrap();
+/// assert_eq!(brd, ndarray::array![[1, 1, 1]]);
+/// assert_eq!(rule, GenerationsRule::default());
+///
+/// assert!(from_rle("x = 2, y = 1\n3o!").is_err());
+/// assert!(from_rle("x = 3, y = 1, rule = B3/S23\n2B!").is_err());
+/// assert!(from_rle("3o!").is_err());
+///
+/// // Counts and sizes too large to hold or allocate.
+/// assert!(from_rle("x = 3, y = 1\n99999999999999999999999o!").is_err());
+/// assert!(from_rle("x = 3, y = 3\n18446744073709551615$2$o!").is_err());
+/// assert!(from_rle("x = 3, y = 1\n18446744073709551615b2o!").is_err());
+/// assert!(from_rle("x = 1000000, y = 1000000\no!").is_err());
+/// assert!(from_rle("x = 18446744073709551615, y = 2\no!").is_err());
+/// ```
+pub fn from_rle(text: &str) -> Result<(ndarray::Array2<u8>, GenerationsRule), RleError> {
+    return from_rle_with_max_cells(text, Some(MAX_RLE_CELLS))
+}
+
+/// Read a board in RLE format, see `from_rle`.
+///
+/// # Arguments
+/// `text` - the contents of an `.rle` file.
+/// `max_cells` - the most cells the board may have, checked before the board is allocated.
+/// `None` means no limit.
+///
+/// # Returns
+/// The board and its rule, or an error if the text is not a valid RLE file or the board is too
+/// large.
+///
+/// ```
+/// use game_of_life::rle::from_rle_with_max_cells;
+///
+/// assert!(from_rle_with_max_cells("x = 3, y = 3\no!", Some(9)).is_ok());
+/// assert!(from_rle_with_max_cells("x = 3, y = 3\no!", Some(8)).is_err());
+/// ```
+pub fn from_rle_with_max_cells(text: &str, max_cells: Option<usize>)
+                               -> Result<(ndarray::Array2<u8>, GenerationsRule), RleError> {
+    let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty() & !line.starts_with('#'));
+    let err = |line: usize, message: &str| RleError { line: line + 1, message: message.to_string() };
+
+    // The header.
+    let (idx, header) = lines.next().ok_or_else(|| err(0, "missing header"))?;
+    let (mut cols, mut rows, mut rule) = (None, None, GenerationsRule::default());
+    for field in header.split(',') {
+        let mut kv = field.splitn(2, '=').map(str::trim);
+        match (kv.next(), kv.next()) {
+            (Some("x"), Some(v)) => cols = v.parse::<usize>().ok(),
+            (Some("y"), Some(v)) => rows = v.parse::<usize>().ok(),
+            (Some("rule"), Some(v)) => rule = v.parse().map_err(|e: crate::rule::RuleParseError| err(idx, &e.to_string()))?,
+            _ => return Err(err(idx, "expected a header like 'x = 3, y = 3, rule = B3/S23'")),
+        }
+    }
+    let (rows, cols) = match (rows, cols) {
+        (Some(rows), Some(cols)) => (rows, cols),
+        _ => return Err(err(idx, "header needs an x and y size")),
+    };
+    match (rows.checked_mul(cols), max_cells) {
+        (None, _) => return Err(err(idx, "board is too large")),
+        (Some(cells), Some(limit)) if cells > limit => {
+            return Err(err(idx, &format!("board has {} cells, more than the limit of {}", cells, limit)));
+        }
+        _ => {}
+    }
+    let mut brd: ndarray::Array2<u8> = ndarray::Array2::zeros((rows, cols));
+
+    // The cells.
+    let (mut r, mut c): (usize, usize) = (0, 0);
+    let mut count: Option<usize> = None;
+    let mut prefix: Option<u8> = None;
+    for (idx, line) in lines {
+        for ch in line.trim().chars() {
+            let run = count.unwrap_or(1);
+            let state: u8 = match ch {
+                '0'..='9' => {
+                    count = count.unwrap_or(0).checked_mul(10).and_then(|n| n.checked_add(ch.to_digit(10).unwrap() as usize));
+                    if count.is_none() { return Err(err(idx, "run count is too large")); }
+                    continue;
+                }
+                'p'..='y' if prefix.is_none() => { prefix = Some(ch as u8 - b'p' + 1); continue; }
+                '!' => return Ok((brd, rule)),
+                '$' => {
+                    r = r.checked_add(run).ok_or_else(|| err(idx, "run count is too large"))?;
+                    c = 0;
+                    count = None;
+                    continue;
+                }
+                'b' | '.' => 0,
+                'o' => 1,
+                'A'..='X' => {
+                    let state = prefix.take().unwrap_or(0) as usize * 24 + (ch as u8 - b'A') as usize + 1;
+                    if state > u8::MAX as usize { return Err(err(idx, "state is out of range")); }
+                    state as u8
+                }
+                _ => return Err(err(idx, &format!("unexpected character '{}'", ch))),
+            };
+            if prefix.is_some() { return Err(err(idx, "state prefix must be followed by a letter")); }
+            if state >= rule.states() { return Err(err(idx, &format!("state {} is not in rule {}", state, rule))); }
+            if (r >= rows) | (run > cols - c) { return Err(err(idx, "cells run past the size in the header")); }
+            for cc in c..c + run { brd[[r, cc]] = state; }
+            c += run;
+            count = None;
+        }
+    }
+    return Err(err(text.lines().count().saturating_sub(1), "missing '!' at the end of the pattern"))
+}
+
+/// Write a board to a `.rle` file, see `to_rle`.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rule` - the rule of the pattern.
+/// `path` - the file to write.
+pub fn save_rle<P: AsRef<std::path::Path>>(brd: &ndarray::Array2<u8>,
+                                           rule: &GenerationsRule,
+                                           path: P) -> std::io::Result<()> {
+    return std::fs::write(path, to_rle(brd, rule))
+}
@@ -0,0 +1,347 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use core::str::FromStr;
+
+/// An outer-totalistic rule in B/S notation, e.g. `B3/S23` for Conway's game of life.
+///
+/// ```
+/// use game_of_life::rule::Rule;
+///
+/// let highlife = Rule::new(&[3, 6], &[2, 3]);
+/// assert!(highlife.born(6));
+/// assert!(!highlife.survives(6));
+/// assert_eq!(highlife.to_string(), "B36/S23");
+///
+/// // Parsing needs the `std` feature.
+/// #[cfg(feature = "std")]
+/// {
+///     assert_eq!("B36/S23".parse::<Rule>().unwrap(), highlife);
+///     assert_eq!("b3/s23".parse::<Rule>().unwrap(), Rule::conway());
+///     assert!("B9/S23".parse::<Rule>().is_err());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// Bit `n` is set when a dead cell with `n` live neighbors is born.
+    birth: u16,
+    /// Bit `n` is set when a live cell with `n` live neighbors survives.
+    survive: u16,
+}
+
+impl Rule {
+    /// Build a rule from the neighbor counts that cause a birth and those that allow survival.
+    ///
+    /// # Arguments
+    /// `birth` - the neighbor counts that cause a dead cell to be born.
+    /// `survive` - the neighbor counts that allow a live cell to survive.
+    ///
+    /// # Panics
+    /// If any count is greater than 8.
+    pub fn new(birth: &[u8], survive: &[u8]) -> Rule {
+        let mut rule = Rule { birth: 0, survive: 0 };
+        for &n in birth { assert!(n <= 8, "neighbor count {} is out of range", n); rule.birth |= 1 << n; }
+        for &n in survive { assert!(n <= 8, "neighbor count {} is out of range", n); rule.survive |= 1 << n; }
+        return rule
+    }
+
+    /// Conway's game of life, `B3/S23`.
+    pub fn conway() -> Rule {
+        return Rule::new(&[3], &[2, 3])
+    }
+
+    /// Whether a dead cell with `count` live neighbors is born.
+    pub fn born(&self, count: u8) -> bool {
+        return (count <= 8) && (self.birth & (1 << count) != 0)
+    }
+
+    /// Whether a live cell with `count` live neighbors survives.
+    pub fn survives(&self, count: u8) -> bool {
+        return (count <= 8) && (self.survive & (1 << count) != 0)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Rule {
+        return Rule::conway()
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B")?;
+        for n in 0..=8 { if self.born(n) { write!(f, "{}", n)?; } }
+        write!(f, "/S")?;
+        for n in 0..=8 { if self.survives(n) { write!(f, "{}", n)?; } }
+        return Ok(())
+    }
+}
+
+/// A rule string could not be parsed.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleParseError {
+    pub rule: String,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid rule '{}', expected B/S notation such as B3/S23", self.rule)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RuleParseError {}
+
+/// Parse the digits following a `B` or `S` prefix.
+#[cfg(feature = "std")]
+fn parse_counts(part: &str, prefix: char) -> Option<Vec<u8>> {
+    let mut chars = part.chars();
+    if chars.next()?.to_ascii_uppercase() != prefix { return None; }
+    let mut counts: Vec<u8> = vec![];
+    for ch in chars {
+        let n = ch.to_digit(10)?;
+        if n > 8 { return None; }
+        counts.push(n as u8);
+    }
+    return Some(counts)
+}
+
+#[cfg(feature = "std")]
+impl FromStr for Rule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Rule, RuleParseError> {
+        let err = || RuleParseError { rule: s.to_string() };
+        let parts: Vec<&str> = s.trim().split('/').collect();
+        if parts.len() != 2 { return Err(err()); }
+        let birth = parse_counts(parts[0], 'B').ok_or_else(err)?;
+        let survive = parse_counts(parts[1], 'S').ok_or_else(err)?;
+        return Ok(Rule::new(&birth, &survive))
+    }
+}
+
+/// Something that decides a cell's next state from its current state and live neighbor count.
+pub trait Transition {
+    /// Whether a cell is alive in the next generation.
+    ///
+    /// # Arguments
+    /// `alive` - whether the cell is alive now.
+    /// `count` - the number of live neighbors.
+    fn next_state(&self, alive: bool, count: u8) -> bool;
+}
+
+impl Transition for Rule {
+    fn next_state(&self, alive: bool, count: u8) -> bool {
+        return if alive { self.survives(count) } else { self.born(count) }
+    }
+}
+
+/// A threshold rule: a live cell survives when its neighbor count is within
+/// `survive_min..=survive_max`, and a dead cell is born when its count is within
+/// `birth_min..=birth_max`. This is more natural than B/S digit sets for "Larger than Life" style
+/// rules with large neighborhoods.
+///
+/// ```
+/// use game_of_life::rule::{RangeRule, Rule, Transition};
+///
+/// let conway = RangeRule { survive_min: 2, survive_max: 3, birth_min: 3, birth_max: 3 };
+/// for count in 0..=8 {
+///     for &alive in &[false, true] {
+///         assert_eq!(conway.next_state(alive, count), Rule::conway().next_state(alive, count));
+///     }
+/// }
+///
+/// let bugs = RangeRule { survive_min: 34, survive_max: 58, birth_min: 34, birth_max: 45 };
+/// assert!(bugs.next_state(false, 40));
+/// assert!(!bugs.next_state(false, 50));
+/// assert!(bugs.next_state(true, 50));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RangeRule {
+    pub survive_min: u8,
+    pub survive_max: u8,
+    pub birth_min: u8,
+    pub birth_max: u8,
+}
+
+impl Transition for RangeRule {
+    fn next_state(&self, alive: bool, count: u8) -> bool {
+        return if alive { (self.survive_min..=self.survive_max).contains(&count) }
+               else { (self.birth_min..=self.birth_max).contains(&count) }
+    }
+}
+
+/// A Generations rule: live cells that fail to survive don't die straight away, but age through
+/// `states - 2` dying states before becoming dead. Only live cells count as neighbors, and dying
+/// cells can't be born again until they are dead. Cells are 0 for dead, 1 for alive and 2 up to
+/// `states - 1` for dying.
+///
+/// Written as `B2/S/C3`, a B/S rule followed by the number of states. The `C` is optional when
+/// parsing, and a rule without a state count has 2 states, so every B/S rule is also a
+/// Generations rule.
+///
+/// ```
+/// use game_of_life::rule::{GenerationsRule, Rule};
+///
+/// let brain = GenerationsRule::brians_brain();
+/// assert_eq!(brain.states(), 3);
+/// assert_eq!(brain.next_state(0, 2), 1);
+/// assert_eq!(brain.next_state(1, 2), 2);
+/// assert_eq!(brain.next_state(2, 2), 0);
+/// assert_eq!(brain.to_string(), "B2/S/C3");
+///
+/// let life = GenerationsRule::new(Rule::conway(), 2);
+/// assert_eq!(life.to_string(), "B3/S23");
+///
+/// // Parsing needs the `std` feature.
+/// #[cfg(feature = "std")]
+/// {
+///     assert_eq!("B2/S/C3".parse::<GenerationsRule>().unwrap(), brain);
+///     assert_eq!("b2/s/3".parse::<GenerationsRule>().unwrap(), brain);
+///     assert_eq!("B3/S23".parse::<GenerationsRule>().unwrap(), life);
+///     assert!("B3/S23/C1".parse::<GenerationsRule>().is_err());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenerationsRule {
+    rule: Rule,
+    states: u8,
+}
+
+impl GenerationsRule {
+    /// Build a Generations rule from a B/S rule and a number of states.
+    ///
+    /// # Arguments
+    /// `rule` - the births and survivals of live cells.
+    /// `states` - the number of states, including dead and alive.
+    ///
+    /// # Panics
+    /// If there are fewer than 2 states.
+    pub fn new(rule: Rule, states: u8) -> GenerationsRule {
+        assert!(states >= 2, "a rule needs at least 2 states, not {}", states);
+        return GenerationsRule { rule, states }
+    }
+
+    /// Brian's Brain, `B2/S/C3`: a dead cell with exactly two live neighbors is born, and every
+    /// live cell starts dying straight away.
+    pub fn brians_brain() -> GenerationsRule {
+        return GenerationsRule::new(Rule::new(&[2], &[]), 3)
+    }
+
+    /// The births and survivals of live cells.
+    pub fn rule(&self) -> Rule {
+        return self.rule
+    }
+
+    /// The number of states, including dead and alive.
+    pub fn states(&self) -> u8 {
+        return self.states
+    }
+
+    /// The next state of a cell.
+    ///
+    /// # Arguments
+    /// `state` - the state of the cell now.
+    /// `count` - the number of live neighbors.
+    pub fn next_state(&self, state: u8, count: u8) -> u8 {
+        return match state {
+            0 => self.rule.born(count) as u8,
+            1 if self.rule.survives(count) => 1,
+            _ => (state + 1) % self.states,
+        }
+    }
+}
+
+impl Default for GenerationsRule {
+    fn default() -> GenerationsRule {
+        return GenerationsRule::from(Rule::conway())
+    }
+}
+
+impl From<Rule> for GenerationsRule {
+    fn from(rule: Rule) -> GenerationsRule {
+        return GenerationsRule::new(rule, 2)
+    }
+}
+
+impl fmt::Display for GenerationsRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.rule)?;
+        if self.states > 2 { write!(f, "/C{}", self.states)?; }
+        return Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for GenerationsRule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<GenerationsRule, RuleParseError> {
+        let err = || RuleParseError { rule: s.to_string() };
+        let s = s.trim();
+        let parts: Vec<&str> = s.splitn(3, '/').collect();
+        let rule: Rule = parts[..parts.len().min(2)].join("/").parse().map_err(|_| err())?;
+        let states: u8 = match parts.get(2) {
+            Some(count) => count.trim_start_matches(['C', 'c']).parse().map_err(|_| err())?,
+            None => 2,
+        };
+        if states < 2 { return Err(err()); }
+        return Ok(GenerationsRule::new(rule, states))
+    }
+}
+
+/// Rules that change over the course of a run: each rule applies from the generation it starts at
+/// until the next one starts.
+///
+/// ```
+/// use game_of_life::rule::{Rule, RuleSchedule};
+///
+/// let highlife: Rule = "B36/S23".parse().unwrap();
+/// let schedule = RuleSchedule::new(Rule::conway()).switch_at(100, highlife);
+/// assert_eq!(*schedule.rule_at(0), Rule::conway());
+/// assert_eq!(*schedule.rule_at(99), Rule::conway());
+/// assert_eq!(*schedule.rule_at(100), highlife);
+/// assert_eq!(*schedule.rule_at(5000), highlife);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSchedule {
+    /// The generation each rule starts at, in order, starting with 0.
+    changes: Vec<(usize, Rule)>,
+}
+
+#[cfg(feature = "std")]
+impl RuleSchedule {
+    /// A schedule of a single rule from generation 0.
+    ///
+    /// # Arguments
+    /// `first` - the rule to start with.
+    pub fn new(first: Rule) -> RuleSchedule {
+        return RuleSchedule { changes: vec![(0, first)] }
+    }
+
+    /// Switch to another rule from a later generation on.
+    ///
+    /// # Arguments
+    /// `generation` - the first generation stepped with `rule`.
+    /// `rule` - the rule.
+    ///
+    /// # Panics
+    /// If `generation` is not after the start of the last rule.
+    pub fn switch_at(mut self, generation: usize, rule: Rule) -> RuleSchedule {
+        let &(last, _) = self.changes.last().unwrap();
+        assert!(generation > last, "rule changes must be in order, {} is not after {}", generation, last);
+        self.changes.push((generation, rule));
+        return self
+    }
+
+    /// The rule for stepping from a generation to the next.
+    ///
+    /// # Arguments
+    /// `generation` - the generation being stepped from.
+    pub fn rule_at(&self, generation: usize) -> &Rule {
+        let idx = self.changes.partition_point(|&(start, _)| start <= generation);
+        return &self.changes[idx - 1].1
+    }
+}
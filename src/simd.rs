@@ -0,0 +1,95 @@
+//! Stepping with SIMD, adding up the neighbors of 16 cells at once. Requires the `simd` feature.
+//!
+//! The board is copied into a flat buffer with a border of dead cells, so the neighbors of a row
+//! of cells are the same row of the buffer shifted by one cell in each direction, and can be
+//! summed as whole vectors without special cases for the edges. SSE2 is used on x86_64, where it
+//! is always available; elsewhere, and for the cells left over at the end of each row, the same
+//! sums are done one cell at a time.
+
+use crate::rule::{Rule, Transition};
+
+/// Advance a board by one generation, the same as `utils::next_generation_with_rule`.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rule` - the rule deciding births and survivals.
+///
+/// # Returns
+/// The next generation.
+///
+/// ```
+/// use game_of_life::{rule::Rule, simd::next_generation_simd, utils::{next_generation_with_rule, random_board}};
+///
+/// let highlife: Rule = "B36/S23".parse().unwrap();
+/// let sizes = [(1, 1), (1, 40), (40, 1), (15, 15), (16, 16), (17, 33), (64, 100)];
+/// for (seed, &(rows, cols)) in sizes.iter().enumerate() {
+///     let brd = random_board(rows, cols, 0.4, seed as u64);
+///     for rule in &[Rule::conway(), highlife] {
+///         assert_eq!(next_generation_simd(&brd, rule), next_generation_with_rule(&brd, rule));
+///     }
+/// }
+/// ```
+pub fn next_generation_simd(brd: &ndarray::Array2<u8>, rule: &Rule) -> ndarray::Array2<u8> {
+    let (rows, cols) = brd.dim();
+    let width = cols + 2;
+    let mut padded: Vec<u8> = vec![0; (rows + 2) * width];
+    for (r, row) in brd.genrows().into_iter().enumerate() {
+        for (dst, &v) in padded[(r + 1) * width + 1..(r + 2) * width - 1].iter_mut().zip(row) { *dst = v; }
+    }
+
+    let births: Vec<u8> = (0..=8).filter(|&n| rule.born(n)).collect();
+    let survivals: Vec<u8> = (0..=8).filter(|&n| rule.survives(n)).collect();
+    let mut next: Vec<u8> = vec![0; rows * cols];
+    for (r, out) in next.chunks_mut(cols.max(1)).enumerate() {
+        let rows3 = &padded[r * width..(r + 3) * width];
+        let done = step_lanes(rows3, width, &births, &survivals, out);
+        for (c, v) in out.iter_mut().enumerate().skip(done) {
+            let count = neighbors(rows3, width, c);
+            *v = rule.next_state(rows3[width + c + 1] == 1, count) as u8;
+        }
+    }
+    return ndarray::Array2::from_shape_vec((rows, cols), next).unwrap()
+}
+
+/// The live neighbors of column `c` of the middle of three padded rows.
+fn neighbors(rows3: &[u8], width: usize, c: usize) -> u8 {
+    let (above, middle, below) = (&rows3[..width], &rows3[width..2 * width], &rows3[2 * width..]);
+    return above[c] + above[c + 1] + above[c + 2] + middle[c] + middle[c + 2] + below[c] + below[c + 1] + below[c + 2]
+}
+
+/// Step as many whole vectors of cells of a row as fit, returning the number of cells stepped.
+#[cfg(target_arch = "x86_64")]
+fn step_lanes(rows3: &[u8], width: usize, births: &[u8], survivals: &[u8], out: &mut [u8]) -> usize {
+    use std::arch::x86_64::*;
+    // The number of cells handled at once.
+    const LANES: usize = 16;
+
+    let cols = out.len();
+    let mut c = 0;
+    // SSE2 is part of x86_64, and every load and store is of 16 bytes inside a slice.
+    unsafe {
+        let load = |offset: usize| _mm_loadu_si128(rows3[offset..offset + LANES].as_ptr() as *const __m128i);
+        let matches = |counts: __m128i, set: &[u8]| set.iter().fold(_mm_setzero_si128(), |acc, &n| {
+            _mm_or_si128(acc, _mm_cmpeq_epi8(counts, _mm_set1_epi8(n as i8)))
+        });
+        while c + LANES <= cols {
+            let mut counts = _mm_setzero_si128();
+            for &offset in &[c, c + 1, c + 2, width + c, width + c + 2, 2 * width + c, 2 * width + c + 1, 2 * width + c + 2] {
+                counts = _mm_add_epi8(counts, load(offset));
+            }
+            let alive = _mm_cmpeq_epi8(load(width + c + 1), _mm_set1_epi8(1));
+            let next = _mm_or_si128(_mm_andnot_si128(alive, matches(counts, births)),
+                                    _mm_and_si128(alive, matches(counts, survivals)));
+            let next = _mm_and_si128(next, _mm_set1_epi8(1));
+            _mm_storeu_si128(out[c..c + LANES].as_mut_ptr() as *mut __m128i, next);
+            c += LANES;
+        }
+    }
+    return c
+}
+
+/// Without SSE2, every cell is stepped one at a time.
+#[cfg(not(target_arch = "x86_64"))]
+fn step_lanes(_rows3: &[u8], _width: usize, _births: &[u8], _survivals: &[u8], _out: &mut [u8]) -> usize {
+    return 0
+}
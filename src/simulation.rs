@@ -0,0 +1,149 @@
+use log::{debug, info, log_enabled, Level};
+
+use crate::utils::{self, StepConfig};
+
+/// A function called with the board and its generation after each step.
+pub type Observer = Box<dyn FnMut(&ndarray::Array2<u8>, usize)>;
+
+/// A board stepped one generation at a time, telling observers about each new generation, so
+/// renderers or metrics can follow along without the simulation knowing about them.
+///
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use ndarray::array;
+/// use game_of_life::{simulation::Simulation, utils::{Boundary, StepConfig}};
+///
+/// let seen: Rc<RefCell<Vec<usize>>> = Rc::new(RefCell::new(vec![]));
+/// let populations: Rc<RefCell<Vec<usize>>> = Rc::new(RefCell::new(vec![]));
+///
+/// let torus = StepConfig { boundary: Boundary::Torus, ..Default::default() };
+/// let mut sim = Simulation::new(array![[0, 1, 0], [0, 1, 0], [0, 1, 0]]).with_config(torus);
+/// let log = seen.clone();
+/// sim.on_step(Box::new(move |_, gen| log.borrow_mut().push(gen)));
+/// let log = populations.clone();
+/// sim.on_step(Box::new(move |brd, _| log.borrow_mut().push(game_of_life::utils::population(brd))));
+///
+/// for _ in 0..3 { sim.step(); }
+/// assert_eq!(*seen.borrow(), vec![1, 2, 3]);
+/// // On a 3x3 torus every cell neighbors the whole blinker, so it fills the board and then dies.
+/// assert_eq!(*populations.borrow(), vec![9, 0, 0]);
+/// assert_eq!(sim.generation(), 3);
+/// ```
+///
+/// Each step emits a `debug!` log with the population, and an `info!` log on the generation the
+/// board stabilizes or goes extinct. Resizing the board emits an `info!` log too. Without a
+/// logger at these levels, the board isn't compared or counted.
+///
+/// ```
+/// use std::sync::Mutex;
+/// use ndarray::array;
+/// use game_of_life::simulation::Simulation;
+///
+/// static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+/// struct Capture;
+/// impl log::Log for Capture {
+///     fn enabled(&self, metadata: &log::Metadata) -> bool { metadata.level() <= log::Level::Info }
+///     fn log(&self, record: &log::Record) {
+///         if self.enabled(record.metadata()) { MESSAGES.lock().unwrap().push(record.args().to_string()); }
+///     }
+///     fn flush(&self) {}
+/// }
+/// log::set_logger(&Capture).unwrap();
+/// log::set_max_level(log::LevelFilter::Info);
+///
+/// // A pre-block becomes a block after one generation, and then stays a block.
+/// let mut sim = Simulation::new(array![[0, 0, 0, 0], [0, 1, 1, 0], [0, 1, 0, 0], [0, 0, 0, 0]]);
+/// for _ in 0..4 { sim.step(); }
+/// sim.resize(6, 6);
+/// assert_eq!(sim.board().dim(), (6, 6));
+/// assert_eq!(game_of_life::utils::population(sim.board()), 4);
+/// assert_eq!(*MESSAGES.lock().unwrap(), vec!["board has stabilized at generation 1 with population 4",
+///                                            "board resized from 4x4 to 6x6"]);
+/// ```
+pub struct Simulation {
+    board: ndarray::Array2<u8>,
+    generation: usize,
+    config: StepConfig,
+    observers: Vec<Observer>,
+    /// Whether the last step left the board unchanged, so stabilizing is only logged once.
+    stable: bool,
+}
+
+impl Simulation {
+    /// Start a simulation of Conway's game of life at generation 0.
+    ///
+    /// # Arguments
+    /// `board` - the starting board.
+    pub fn new(board: ndarray::Array2<u8>) -> Simulation {
+        return Simulation { board, generation: 0, config: StepConfig::default(), observers: vec![], stable: false }
+    }
+
+    /// Step with a different rule, boundary or neighborhood.
+    ///
+    /// # Arguments
+    /// `config` - how the board steps.
+    pub fn with_config(mut self, config: StepConfig) -> Simulation {
+        self.config = config;
+        return self
+    }
+
+    /// Register an observer, called after every later step. Observers are called in the order
+    /// they were registered.
+    ///
+    /// # Arguments
+    /// `observer` - the function to call with the new board and its generation.
+    pub fn on_step(&mut self, observer: Observer) {
+        self.observers.push(observer);
+    }
+
+    /// Advance the board by one generation, then tell the observers.
+    pub fn step(&mut self) {
+        let next = if self.config.is_standard() { utils::next_generation_with_rule(&self.board, &self.config.rule) }
+                   else { utils::step(&self.board, &self.config) };
+        self.generation += 1;
+        // Debug is more verbose than info, so this covers every log below.
+        if log_enabled!(Level::Info) {
+            let pop = utils::population(&next);
+            let changed = next != self.board;
+            debug!("generation {} complete: population {}", self.generation, pop);
+            if changed & (pop == 0) { info!("board is extinct at generation {}", self.generation); }
+            if !changed & !self.stable & (pop > 0) {
+                info!("board has stabilized at generation {} with population {}", self.generation - 1, pop);
+            }
+            self.stable = !changed;
+        }
+        self.board = next;
+        for observer in self.observers.iter_mut() { observer(&self.board, self.generation); }
+    }
+
+    /// Change the size of the board, keeping the cells that fit from the top left corner and
+    /// adding dead cells beyond them.
+    ///
+    /// # Arguments
+    /// `rows` - the new number of rows.
+    /// `cols` - the new number of columns.
+    pub fn resize(&mut self, rows: usize, cols: usize) {
+        let (old_rows, old_cols) = self.board.dim();
+        let (keep_rows, keep_cols) = (rows.min(old_rows), cols.min(old_cols));
+        let mut brd: ndarray::Array2<u8> = ndarray::Array2::zeros((rows, cols));
+        brd.slice_mut(ndarray::s![..keep_rows, ..keep_cols]).assign(&self.board.slice(ndarray::s![..keep_rows, ..keep_cols]));
+        self.board = brd;
+        self.stable = false;
+        info!("board resized from {}x{} to {}x{}", old_rows, old_cols, rows, cols);
+    }
+
+    /// The current board.
+    pub fn board(&self) -> &ndarray::Array2<u8> {
+        return &self.board
+    }
+
+    /// The number of steps taken.
+    pub fn generation(&self) -> usize {
+        return self.generation
+    }
+
+    /// How the board steps.
+    pub fn config(&self) -> &StepConfig {
+        return &self.config
+    }
+}
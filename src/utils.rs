@@ -1,4 +1,8 @@
 use itertools::iproduct;
+use log::{debug, info, log_enabled, Level};
+use std::{fmt, thread, sync::RwLock, sync::Arc, sync::mpsc};
+
+use crate::rule::{GenerationsRule, Rule, RuleSchedule, Transition};
 
 /// Split up the board into non-overlapping sub-boards.
 static NUM_ROW_GROUPS: u64 = 3;
@@ -51,12 +55,90 @@ pub fn flip_board(brd: &mut ndarray::Array2<u8>, rows: &usize, cols: &usize) {
 /// let mut arr = array![[1, 2], [3, 4]];
 /// assert_eq!(game_of_life::utils::gather_board_values(&arr, &[(0, 0), (1, 1)]), 5);
 /// ```
-pub fn gather_board_values(brd: &ndarray::Array2<u8>, pos_arr: &[(usize, usize)]) -> u8 {
+pub fn gather_board_values<S: ndarray::Data<Elem = u8>>(brd: &ndarray::ArrayBase<S, ndarray::Ix2>, pos_arr: &[(usize, usize)]) -> u8 {
     let mut ret: u8 = 0;
     for (r0, c0) in pos_arr.iter() { ret += brd[[*r0, *c0]]; }
     return ret
 }
 
+/// Look up a cell by signed coordinates.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `r` - the row of the cell.
+/// `c` - the column of the cell.
+///
+/// # Returns
+/// The value of the cell, or `None` if it is outside of the board.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::get;
+///
+/// let arr = array![[1, 2], [3, 4]];
+/// assert_eq!(get(&arr, 1, 0), Some(3));
+/// assert_eq!(get(&arr, -1, 0), None);
+/// assert_eq!(get(&arr, 0, 2), None);
+/// ```
+pub fn get(brd: &ndarray::Array2<u8>, r: i64, c: i64) -> Option<u8> {
+    if (r < 0) | (c < 0) { return None; }
+    return brd.get([r as usize, c as usize]).copied()
+}
+
+/// How to treat coordinates that fall outside of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// Everything beyond the edge is dead.
+    Fixed,
+    /// The board wraps around at the edges, like a torus.
+    Torus,
+    /// Everything beyond the edge is alive.
+    FixedAlive,
+}
+
+impl Default for Boundary {
+    fn default() -> Boundary {
+        return Boundary::Fixed
+    }
+}
+
+/// Add all the values in a board at a given collection of possibly out-of-range indices.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `pos_arr` - the array of signed positions to sum.
+/// `boundary` - how to treat positions outside of the board.
+///
+/// # Returns
+/// The sum.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{gather_board_values_with_boundary, Boundary};
+///
+/// let arr = array![[1, 2], [3, 4]];
+/// assert_eq!(gather_board_values_with_boundary(&arr, &[(-1, 0), (0, 0), (1, 2)], Boundary::Fixed), 1);
+/// assert_eq!(gather_board_values_with_boundary(&arr, &[(-1, 0), (0, 0), (1, 2)], Boundary::Torus), 7);
+/// assert_eq!(gather_board_values_with_boundary(&arr, &[(-3, -3)], Boundary::Torus), 4);
+/// assert_eq!(gather_board_values_with_boundary(&arr, &[(-1, 0), (0, 0), (1, 2)], Boundary::FixedAlive), 3);
+/// ```
+pub fn gather_board_values_with_boundary(brd: &ndarray::Array2<u8>,
+                                         pos_arr: &[(i64, i64)],
+                                         boundary: Boundary) -> u8 {
+    let rows = brd.shape()[0] as i64;
+    let cols = brd.shape()[1] as i64;
+    let mut ret: u8 = 0;
+    for &(r0, c0) in pos_arr.iter() {
+        ret += match (get(brd, r0, c0), boundary) {
+            (Some(v), _) => v,
+            (None, Boundary::Fixed) => 0,
+            (None, Boundary::FixedAlive) => 1,
+            (None, Boundary::Torus) => brd[[r0.rem_euclid(rows) as usize, c0.rem_euclid(cols) as usize]],
+        };
+    }
+    return ret
+}
+
 /// Determine the sum of all the neighbors of a given cell.
 ///
 /// # Arguments
@@ -67,7 +149,7 @@ pub fn gather_board_values(brd: &ndarray::Array2<u8>, pos_arr: &[(usize, usize)]
 /// `c` - the column of the cell.
 ///
 /// # Returns
-/// The sum of all neighbors of a particular cell.
+/// The sum of all neighbors of a particular cell. The board may be an owned array or a view.
 ///
 /// ```
 /// use ndarray::array;
@@ -92,11 +174,54 @@ pub fn gather_board_values(brd: &ndarray::Array2<u8>, pos_arr: &[(usize, usize)]
 /// assert_eq!(count_neighbors(&arr, &r, &c, &2, &1), 26);
 /// assert_eq!(count_neighbors(&arr, &r, &c, &2, &2), 16);
 /// ```
-pub fn count_neighbors(brd: &ndarray::Array2<u8>,
-                   rows: &usize,
-                   cols: &usize,
-                   r: &usize,
-                   c: &usize) -> u8 {
+///
+/// Views work too, with the edges of the view as the edges of the board:
+///
+/// ```
+/// use ndarray::{s, Array2};
+/// use game_of_life::utils::{count_neighbors, next_generation};
+///
+/// let big: Array2<u8> = Array2::ones((6, 6));
+/// let view = big.slice(s![1..4, 2..5]);
+/// assert_eq!(count_neighbors(&view, &3, &3, &0, &0), 3);
+/// assert_eq!(count_neighbors(&view, &3, &3, &0, &1), 5);
+/// assert_eq!(count_neighbors(&view, &3, &3, &1, &1), 8);
+/// assert_eq!(next_generation(&view), ndarray::array![[1, 0, 1], [0, 0, 0], [1, 0, 1]]);
+/// ```
+///
+/// Boards a single cell thin have no neighbors in the missing directions:
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{count_neighbors, next_generation};
+///
+/// let row = array![[1, 1, 0, 1, 1]];
+/// let counts: Vec<u8> = (0..5).map(|c| count_neighbors(&row, &1, &5, &0, &c)).collect();
+/// assert_eq!(counts, vec![1, 1, 2, 1, 1]);
+///
+/// let column = row.t().to_owned();
+/// let counts: Vec<u8> = (0..5).map(|r| count_neighbors(&column, &5, &1, &r, &0)).collect();
+/// assert_eq!(counts, vec![1, 1, 2, 1, 1]);
+///
+/// assert_eq!(count_neighbors(&array![[1]], &1, &1, &0, &0), 0);
+/// assert_eq!(next_generation(&array![[1]]), array![[0]]);
+/// assert_eq!(next_generation(&row), array![[0, 0, 0, 0, 0]]);
+/// ```
+pub fn count_neighbors<S: ndarray::Data<Elem = u8>>(brd: &ndarray::ArrayBase<S, ndarray::Ix2>,
+                                                    rows: &usize,
+                                                    cols: &usize,
+                                                    r: &usize,
+                                                    c: &usize) -> u8 {
+    // A board one cell thin has no second row or column for the cases below.
+    if (*rows < 2) | (*cols < 2) {
+        let mut ret: u8 = 0;
+        for r0 in r.saturating_sub(1)..=r + 1 {
+            for c0 in c.saturating_sub(1)..=c + 1 {
+                if (r0, c0) != (*r, *c) { ret += brd.get([r0, c0]).copied().unwrap_or(0); }
+            }
+        }
+        return ret
+    }
     // Upper left corner
     return if (*r == 0) & (*c == 0) { gather_board_values(brd, &[
         (0, 1),
@@ -157,23 +282,252 @@ pub fn count_neighbors(brd: &ndarray::Array2<u8>,
         (*r, c - 1)]) }
 }
 
-/// Print a board.
+/// Determine the sum of all the neighbors of a given cell, treating positions beyond the edge of
+/// the board according to a boundary policy.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `r` - the row of the cell.
+/// `c` - the column of the cell.
+/// `boundary` - how to treat neighbors outside of the board.
+///
+/// # Returns
+/// The sum of all neighbors of a particular cell.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{count_neighbors_with_boundary, Boundary};
+///
+/// let arr = array![[1, 1, 0],
+///                  [1, 0, 0],
+///                  [0, 0, 1]];
+///
+/// // The upper left corner has 5 neighbor slots beyond the edge of the board.
+/// assert_eq!(count_neighbors_with_boundary(&arr, &3, &3, &0, &0, Boundary::Fixed), 2);
+/// assert_eq!(count_neighbors_with_boundary(&arr, &3, &3, &0, &0, Boundary::FixedAlive), 7);
+/// assert_eq!(count_neighbors_with_boundary(&arr, &3, &3, &0, &0, Boundary::Torus), 3);
+/// assert_eq!(count_neighbors_with_boundary(&arr, &3, &3, &1, &1, Boundary::FixedAlive), 4);
+/// ```
+pub fn count_neighbors_with_boundary(brd: &ndarray::Array2<u8>,
+                                     rows: &usize,
+                                     cols: &usize,
+                                     r: &usize,
+                                     c: &usize,
+                                     boundary: Boundary) -> u8 {
+    if boundary == Boundary::Fixed { return count_neighbors(brd, rows, cols, r, c); }
+    let (r, c) = (*r as i64, *c as i64);
+    return gather_board_values_with_boundary(brd, &[
+        (r - 1, c - 1),
+        (r - 1, c),
+        (r - 1, c + 1),
+        (r, c + 1),
+        (r + 1, c + 1),
+        (r + 1, c),
+        (r + 1, c - 1),
+        (r, c - 1)], boundary)
+}
+
+/// Print a board. The whole frame is written and flushed at once, so it doesn't flicker or get
+/// interleaved with other output.
 ///
 /// # Arguments
 /// `brd` - the board.
 /// `rows` - the number of rows.
 /// `cols` - the number of columns.
+/// `frame_num` - the frame number in the header.
 pub fn print_board(brd: &ndarray::Array2<u8>,
                rows: &usize,
                cols: &usize,
                frame_num: &usize) {
-    println!("{} {}", "Frame", frame_num);
+    use std::io::Write;
+
+    let frame = format_board(brd, rows, cols, frame_num);
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(frame.as_bytes()).and_then(|_| stdout.flush()).expect("failed printing to stdout");
+}
+
+/// Build the text that `print_board` prints: a `Frame N` header, then one line per row with each
+/// cell followed by a space.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `frame_num` - the frame number in the header.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::format_board;
+///
+/// let brd = array![[0, 1, 0], [1, 1, 1]];
+/// assert_eq!(format_board(&brd, &2, &3, &7), "Frame 7\n0 1 0 \n1 1 1 \n");
+/// ```
+pub fn format_board(brd: &ndarray::Array2<u8>,
+                    rows: &usize,
+                    cols: &usize,
+                    frame_num: &usize) -> String {
+    use std::fmt::Write;
+
+    // Room for the header and two characters per cell, so single digit cells never reallocate.
+    let mut out = String::with_capacity(32 + rows * (2 * cols + 1));
+    writeln!(out, "Frame {}", frame_num).unwrap();
+    for r in 0..*rows {
+        for c in 0..*cols {
+            write!(out, "{} ", brd[[r, c]]).unwrap();
+        }
+        out.push('\n');
+    }
+    return out
+}
+
+/// Print a board as `print_board` does, with row and column indices along the edges, see
+/// `format_board_with_labels`.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `frame_num` - the frame number in the header.
+/// `every` - the spacing of the labels.
+pub fn print_board_with_labels(brd: &ndarray::Array2<u8>,
+                               rows: &usize,
+                               cols: &usize,
+                               frame_num: &usize,
+                               every: &usize) {
+    use std::io::Write;
+
+    let frame = format_board_with_labels(brd, rows, cols, frame_num, every);
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(frame.as_bytes()).and_then(|_| stdout.flush()).expect("failed printing to stdout");
+}
+
+/// Build the text of `format_board` with every `every`th row and column labeled by its index. Row
+/// labels are right-aligned in a margin on the left. Column labels are written downwards above
+/// their column, one digit per line, so multi-digit indices stay over the cell they label.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `frame_num` - the frame number in the header.
+/// `every` - the spacing of the labels, counting from row and column 0.
+///
+/// # Panics
+/// If `every` is 0.
+///
+/// ```
+/// use game_of_life::utils::format_board_with_labels;
+///
+/// let brd = ndarray::Array2::<u8>::zeros((11, 12));
+/// let text = format_board_with_labels(&brd, &11, &12, &0, &5);
+/// let lines: Vec<&str> = text.lines().collect();
+/// assert_eq!(lines[0], "Frame 0");
+/// assert_eq!(lines[1], "                       1");
+/// assert_eq!(lines[2], "   0         5         0");
+/// assert_eq!(lines[3], " 0 0 0 0 0 0 0 0 0 0 0 0 0 ");
+/// assert_eq!(lines[4], "   0 0 0 0 0 0 0 0 0 0 0 0 ");
+/// assert_eq!(lines[8], " 5 0 0 0 0 0 0 0 0 0 0 0 0 ");
+/// assert_eq!(lines[13], "10 0 0 0 0 0 0 0 0 0 0 0 0 ");
+/// ```
+pub fn format_board_with_labels(brd: &ndarray::Array2<u8>,
+                                rows: &usize,
+                                cols: &usize,
+                                frame_num: &usize,
+                                every: &usize) -> String {
+    use std::fmt::Write;
+
+    assert!(*every > 0, "labels need a spacing of at least 1");
+    let digits = |n: usize| n.to_string().len();
+    let margin = digits(rows.saturating_sub(1) / every * every);
+    let depth = digits(cols.saturating_sub(1) / every * every);
+
+    let mut out = String::new();
+    writeln!(out, "Frame {}", frame_num).unwrap();
+    for line in 0..depth {
+        let mut header = " ".repeat(margin + 1);
+        for c in 0..*cols {
+            let label = format!("{:>1$}", c, depth);
+            header.push(if c % every == 0 { label.as_bytes()[line] as char } else { ' ' });
+            header.push(' ');
+        }
+        out.push_str(header.trim_end());
+        out.push('\n');
+    }
     for r in 0..*rows {
+        if r % every == 0 { write!(out, "{:>1$} ", r, margin).unwrap(); }
+        else { out.push_str(&" ".repeat(margin + 1)); }
         for c in 0..*cols {
-            print!("{} ", brd[[r, c]]);
+            write!(out, "{} ", brd[[r, c]]).unwrap();
+        }
+        out.push('\n');
+    }
+    return out
+}
+
+/// Print one page of a board too tall for the terminal, see `format_board_paged`.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `page` - the page to print, counting from 0.
+/// `page_rows` - the number of rows on a page.
+///
+/// # Returns
+/// The page printed, which is the last page if `page` is past the end.
+pub fn print_board_paged(brd: &ndarray::Array2<u8>, page: usize, page_rows: usize) -> usize {
+    use std::io::Write;
+
+    let (page, text) = format_board_paged(brd, page, page_rows);
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(text.as_bytes()).and_then(|_| stdout.flush()).expect("failed printing to stdout");
+    return page
+}
+
+/// Build the text of one page of a board: a `Page P/N, rows A-B` header, then rows
+/// `page * page_rows` up to `(page + 1) * page_rows` in the format of `format_board`. The last
+/// page may be shorter.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `page` - the page, counting from 0. A page past the end shows the last page instead.
+/// `page_rows` - the number of rows on a page.
+///
+/// # Returns
+/// The page shown and its text.
+///
+/// # Panics
+/// If `page_rows` is 0.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::format_board_paged;
+///
+/// let mut brd: Array2<u8> = Array2::zeros((5, 2));
+/// brd[[4, 1]] = 1;
+/// assert_eq!(format_board_paged(&brd, 0, 2), (0, "Page 1/3, rows 0-1\n0 0 \n0 0 \n".to_string()));
+/// assert_eq!(format_board_paged(&brd, 2, 2), (2, "Page 3/3, rows 4-4\n0 1 \n".to_string()));
+/// assert_eq!(format_board_paged(&brd, 9, 2), format_board_paged(&brd, 2, 2));
+/// ```
+pub fn format_board_paged(brd: &ndarray::Array2<u8>, page: usize, page_rows: usize) -> (usize, String) {
+    use std::fmt::Write;
+
+    assert!(page_rows > 0, "a page needs at least 1 row");
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    let pages = rows.div_ceil(page_rows).max(1);
+    let page = page.min(pages - 1);
+    let start = page * page_rows;
+    let end = (start + page_rows).min(rows);
+
+    let mut out = format!("Page {}/{}, rows {}-{}\n", page + 1, pages, start, end.saturating_sub(1));
+    for r in start..end {
+        for c in 0..cols {
+            write!(out, "{} ", brd[[r, c]]).unwrap();
         }
-        print!("{}", "\n");
+        out.push('\n');
     }
+    return (page, out)
 }
 
 /// Iterate through a rectangular sub-board and return an array of tuples each of which designates a
@@ -202,22 +556,63 @@ pub fn print_board(brd: &ndarray::Array2<u8>,
 /// let c = arr.shape()[1];
 /// assert_eq!(capture_moves(&arr, &r, &c, &0, &2, &0, &1), vec![(0, 0, 1), (1, 0, 1)]);
 /// ```
-pub fn capture_moves(brd: &ndarray::Array2<u8>,
-                 rows: &usize,
-                 cols: &usize,
-                 start_row: &usize,
-                 stop_row: &usize,
-                 start_col: &usize,
-                 stop_col: &usize) -> Vec<(usize, usize, u8)> {
+pub fn capture_moves<S: ndarray::Data<Elem = u8>>(brd: &ndarray::ArrayBase<S, ndarray::Ix2>,
+                                                  rows: &usize,
+                                                  cols: &usize,
+                                                  start_row: &usize,
+                                                  stop_row: &usize,
+                                                  start_col: &usize,
+                                                  stop_col: &usize) -> Vec<(usize, usize, u8)> {
+    return capture_moves_with_rule(brd, rows, cols, start_row, stop_row, start_col, stop_col,
+                                   &Rule::conway())
+}
+
+/// Iterate through a rectangular sub-board and return an array of tuples each of which designates a
+/// change to the original board, under an arbitrary rule such as a `Rule` or a `RangeRule`.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `start_row` - the row the block starts on.
+/// `stop_row` - the row the block stops on.
+/// `start_col` - the column the block starts on.
+/// `stop_col` - the column the block stops on.
+/// `rule` - the rule deciding births and survivals.
+///
+/// # Returns
+/// An array of moves, specifying the value in a board position.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::{rule::Rule, utils::capture_moves_with_rule};
+///
+/// // A dead cell with six neighbors is born under HighLife, but not under Conway.
+/// let arr = array![[1, 1, 1],
+///                  [0, 0, 0],
+///                  [1, 1, 1]];
+/// let highlife: Rule = "B36/S23".parse().unwrap();
+/// assert!(capture_moves_with_rule(&arr, &3, &3, &1, &2, &1, &2, &highlife).contains(&(1, 1, 1)));
+/// assert!(capture_moves_with_rule(&arr, &3, &3, &1, &2, &1, &2, &Rule::conway()).is_empty());
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn capture_moves_with_rule<S: ndarray::Data<Elem = u8>, R: Transition>(brd: &ndarray::ArrayBase<S, ndarray::Ix2>,
+                                                                           rows: &usize,
+                                                                           cols: &usize,
+                                                                           start_row: &usize,
+                                                                           stop_row: &usize,
+                                                                           start_col: &usize,
+                                                                           stop_col: &usize,
+                                                                           rule: &R) -> Vec<(usize, usize, u8)> {
     let mut moves: Vec<(usize, usize, u8)> = vec![];
     let mut count: u8;
     for (r, c) in iproduct!(*start_row..*stop_row, *start_col..*stop_col) {
         count = count_neighbors(brd, rows, cols, &r, &c);
-        // These are the rules of the game of life - determining whether a cell lives or dies by
+        // These are the rules of the game - determining whether a cell lives or dies by
         // considering its neighbors.
-        if (brd[[r, c]] == 1) & ((count < 2) | (count > 3)) {
+        if (brd[[r, c]] == 1) & !rule.next_state(true, count) {
             moves.push((r, c, 0)); }
-        else if count == 3 {
+        else if rule.next_state(false, count) {
             moves.push((r, c, 1)); }
     }
     return moves;
@@ -275,14 +670,18 @@ pub fn get_groups(num: u64, num_groups: u64) -> Vec<usize> {
 pub fn get_subgrids(rows: usize, cols: usize) -> Vec<(usize, usize, usize, usize)>{
     let row_groups: Vec<usize> = get_groups(rows as u64, NUM_ROW_GROUPS);
     let col_groups: Vec<usize> = get_groups(cols as u64, NUM_COL_GROUPS);
+    return subgrids_from_groups(&row_groups, &col_groups)
+}
 
+/// Construct the subgrids formed by every combination of a row group and a column group.
+fn subgrids_from_groups(row_groups: &[usize], col_groups: &[usize]) -> Vec<(usize, usize, usize, usize)> {
     let mut extents: Vec<(usize, usize, usize, usize)> = vec![];
 
     let mut row_first_idx: usize = 0;
     let mut col_first_idx: usize;
-    for r_len in &row_groups {
+    for r_len in row_groups {
         col_first_idx = 0;
-        for c_len in &col_groups {
+        for c_len in col_groups {
             extents.push((row_first_idx,
                           row_first_idx + r_len,
                           col_first_idx,
@@ -292,4 +691,3863 @@ pub fn get_subgrids(rows: usize, cols: usize) -> Vec<(usize, usize, usize, usize
         row_first_idx += r_len;
     }
     return extents
-}
\ No newline at end of file
+}
+
+/// Like `get_subgrids`, but the number of row and column groups is chosen from the dimensions so
+/// that there are roughly `target_tiles` subgrids and each one is as close to square as possible.
+///
+/// # Arguments
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `target_tiles` - the desired number of subgrids.
+///
+/// # Returns
+/// A vector of tuples denoting subgrids, with each tuple containing (ri, rf, ci, cf).
+///
+/// ```
+/// use game_of_life::utils::{get_subgrids, get_subgrids_balanced};
+///
+/// // A square board is split just like `get_subgrids`.
+/// assert_eq!(get_subgrids_balanced(15, 15, 9), get_subgrids(15, 15));
+///
+/// // A wide, short board is split into columns only, rather than 3 rows of 1333-wide tiles.
+/// let extents = get_subgrids_balanced(4, 4000, 9);
+/// assert_eq!(extents.len(), 9);
+/// assert!(extents.iter().all(|&(ri, rf, ci, cf)| (ri, rf) == (0, 4) && cf - ci < 500));
+/// ```
+pub fn get_subgrids_balanced(rows: usize,
+                             cols: usize,
+                             target_tiles: usize) -> Vec<(usize, usize, usize, usize)> {
+    let target = target_tiles.max(1);
+    let mut best: (u64, u64) = (1, 1);
+    let mut best_cost: f64 = f64::INFINITY;
+    for num_row_groups in 1..=target.min(rows.max(1)) {
+        for num_col_groups in 1..=target.min(cols.max(1)) {
+            let tiles = num_row_groups * num_col_groups;
+            // Penalize both elongated tiles and straying from the target number of tiles.
+            let aspect = (rows.max(1) as f64 / num_row_groups as f64)
+                / (cols.max(1) as f64 / num_col_groups as f64);
+            let cost = aspect.ln().abs() + (tiles as f64 / target as f64).ln().abs();
+            let best_tiles = best.0 * best.1;
+            if (cost < best_cost - 1e-9) | (((cost - best_cost).abs() <= 1e-9) & (tiles as u64 > best_tiles)) {
+                best = (num_row_groups as u64, num_col_groups as u64);
+                best_cost = cost;
+            }
+        }
+    }
+    let row_groups: Vec<usize> = get_groups(rows as u64, best.0);
+    let col_groups: Vec<usize> = get_groups(cols as u64, best.1);
+    return subgrids_from_groups(&row_groups, &col_groups)
+}
+
+/// Count the live cells on a board.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The number of live cells.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::population;
+///
+/// assert_eq!(population(&array![[0, 1, 0], [1, 1, 0]]), 3);
+/// ```
+pub fn population(brd: &ndarray::Array2<u8>) -> usize {
+    return brd.iter().filter(|&&v| v == 1).count()
+}
+
+/// Advance a shared board by one generation, computing the moves for each subgrid in its own
+/// thread and then writing them to the board.
+///
+/// Emits a `debug!` log per generation with the population, and an `info!` log on the generation
+/// the board goes extinct. Without a logger at these levels the population isn't counted. Nothing
+/// is remembered between calls, so the `info!` log when a board stabilizes comes from
+/// `simulation::Simulation` instead.
+///
+/// # Arguments
+/// `data_board` - the shared board.
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `extents` - the subgrids, as returned by `get_subgrids`.
+/// `rule` - the rule deciding births and survivals.
+///
+/// # Returns
+/// The number of cells that changed.
+///
+/// ```
+/// use ndarray::array;
+/// use std::sync::{Arc, RwLock};
+/// use game_of_life::{rule::Rule, utils::{get_subgrids, step_parallel}};
+///
+/// let brd = Arc::new(RwLock::new(array![[0, 0, 0, 0, 0],
+///                                       [0, 0, 1, 0, 0],
+///                                       [0, 0, 1, 0, 0],
+///                                       [0, 0, 1, 0, 0],
+///                                       [0, 0, 0, 0, 0]]));
+/// assert_eq!(step_parallel(&brd, &5, &5, &get_subgrids(5, 5), &Rule::conway()), 4);
+/// assert_eq!(*brd.read().unwrap(), array![[0, 0, 0, 0, 0],
+///                                         [0, 0, 0, 0, 0],
+///                                         [0, 1, 1, 1, 0],
+///                                         [0, 0, 0, 0, 0],
+///                                         [0, 0, 0, 0, 0]]);
+/// ```
+pub fn step_parallel(data_board: &Arc<RwLock<ndarray::Array2<u8>>>,
+                     rows: &usize,
+                     cols: &usize,
+                     extents: &[(usize, usize, usize, usize)],
+                     rule: &Rule) -> usize {
+    // Create a send / receive pair. A sender will calculate the moves for a particular subgrid
+    // and send them to the receiver.
+    let (tx, rx) = mpsc::channel::<(usize, usize, u8)>();
+    let mut handles: Vec<thread::JoinHandle<()>> = vec![];
+
+    // Loop over subgrids.
+    for &(r0, rl, c0, cl) in extents {
+        // Use Arc to create a new reference to the board.
+        let par_data = data_board.clone();
+        // Create a new reference to the sender.
+        let par_tx = tx.clone();
+        let (rows, cols, rule) = (*rows, *cols, *rule);
+        handles.push(
+            // Create a thread that takes a subgrid (board and boundaries), finds the moves
+            // and sends them to the receiver.
+            thread::spawn(move ||
+                {
+                    for mv in capture_moves_with_rule(&(par_data.read().unwrap()),
+                                                      &rows,
+                                                      &cols,
+                                                      &r0,
+                                                      &rl,
+                                                      &c0,
+                                                      &cl,
+                                                      &rule) { par_tx.send(mv).unwrap(); }
+                }
+            )
+        );
+    }
+
+    // Join all the threads.
+    for h in handles { h.join().unwrap(); }
+
+    // Drop the sender; otherwise looping over received data will hang.
+    drop(tx);
+
+    // Moves arrive in whatever order the threads finish, which only doesn't matter if no two of
+    // them touch the same cell.
+    let moves: Vec<(usize, usize, u8)> = rx.iter().collect();
+    debug_assert!(verify_disjoint_moves(&moves), "subgrids overlap, so the result depends on thread timing");
+
+    // Loop over the moves, writing each change to the board in a single write lock.
+    let mut changed: usize = 0;
+    {
+        let mut brd = data_board.write().unwrap();
+        for (r, c, v) in moves {
+            if brd[[r, c]] != v { changed += 1; }
+            brd[[r, c]] = v;
+        }
+    }
+
+    // Debug is more verbose than info, so this covers both logs below.
+    if log_enabled!(Level::Info) {
+        let pop = population(&data_board.read().unwrap());
+        debug!("generation complete: population {}, {} cells changed", pop, changed);
+        // Going extinct changes every cell that was alive, so it only happens once.
+        if (pop == 0) & (changed > 0) { info!("board is extinct"); }
+    }
+    return changed
+}
+
+/// Check that no two moves target the same cell, so applying them in any order gives the same board.
+///
+/// # Arguments
+/// `moves` - the moves, as returned by `capture_moves`.
+///
+/// # Returns
+/// Whether every move targets a different cell.
+///
+/// ```
+/// use game_of_life::utils::verify_disjoint_moves;
+///
+/// assert!(verify_disjoint_moves(&[(0, 0, 1), (0, 1, 0), (1, 0, 1)]));
+/// assert!(!verify_disjoint_moves(&[(0, 0, 1), (0, 1, 0), (0, 0, 0)]));
+/// assert!(!verify_disjoint_moves(&[(2, 3, 1), (2, 3, 1)]));
+/// assert!(verify_disjoint_moves(&[]));
+/// ```
+pub fn verify_disjoint_moves(moves: &[(usize, usize, u8)]) -> bool {
+    let mut seen: std::collections::HashSet<(usize, usize)> = std::collections::HashSet::with_capacity(moves.len());
+    return moves.iter().all(|&(r, c, _)| seen.insert((r, c)))
+}
+
+/// Two boards that were expected to have the same shape do not.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeMismatch {
+    pub left: (usize, usize),
+    pub right: (usize, usize),
+}
+
+impl fmt::Display for ShapeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "board shapes differ: {}x{} vs {}x{}",
+               self.left.0, self.left.1, self.right.0, self.right.1)
+    }
+}
+
+impl std::error::Error for ShapeMismatch {}
+
+/// Check that two boards have the same shape.
+fn check_shapes(a: &ndarray::Array2<u8>, b: &ndarray::Array2<u8>) -> Result<(), ShapeMismatch> {
+    if a.shape() != b.shape() {
+        return Err(ShapeMismatch { left: a.dim(), right: b.dim() });
+    }
+    return Ok(())
+}
+
+/// Count the cells that differ between two boards of the same shape.
+///
+/// # Arguments
+/// `a` - the first board.
+/// `b` - the second board.
+///
+/// # Returns
+/// The number of differing cells, or an error if the shapes differ.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::hamming_distance;
+///
+/// let a = array![[0, 1, 0], [1, 1, 0]];
+/// let b = array![[0, 1, 1], [0, 1, 0]];
+/// assert_eq!(hamming_distance(&a, &b), Ok(2));
+/// assert!(hamming_distance(&a, &array![[0, 1], [1, 1]]).is_err());
+/// ```
+pub fn hamming_distance(a: &ndarray::Array2<u8>, b: &ndarray::Array2<u8>) -> Result<usize, ShapeMismatch> {
+    check_shapes(a, b)?;
+    return Ok(a.iter().zip(b.iter()).filter(|(x, y)| x != y).count())
+}
+
+/// Find the smallest rectangle containing every live cell.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The bounding box as (ri, rf, ci, cf), or `None` if the board is empty.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::bounding_box;
+///
+/// assert_eq!(bounding_box(&array![[0, 0, 0], [0, 1, 0], [0, 0, 1]]), Some((1, 3, 1, 3)));
+/// assert_eq!(bounding_box(&array![[0, 0], [0, 0]]), None);
+/// ```
+pub fn bounding_box(brd: &ndarray::Array2<u8>) -> Option<(usize, usize, usize, usize)> {
+    let mut extent: Option<(usize, usize, usize, usize)> = None;
+    for ((r, c), &v) in brd.indexed_iter() {
+        if v != 1 { continue; }
+        extent = Some(match extent {
+            None => (r, r + 1, c, c + 1),
+            Some((ri, rf, ci, cf)) => (ri.min(r), rf.max(r + 1), ci.min(c), cf.max(c + 1)),
+        });
+    }
+    return extent
+}
+
+/// Find the region a pattern can reach within some generations. Life spreads at most one cell a
+/// generation, so after `generations` steps every live cell is within that many cells of the
+/// current bounding box, and stepping only this region gives the same result as stepping the
+/// whole board.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `generations` - the number of generations.
+///
+/// # Returns
+/// The bounding box grown by `generations` on every side and clamped to the board, as
+/// (ri, rf, ci, cf) like `bounding_box`, or (0, 0, 0, 0) if the board is empty.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::reachable_bounds;
+///
+/// let mut brd: Array2<u8> = Array2::zeros((11, 11));
+/// brd[[5, 5]] = 1;
+/// assert_eq!(reachable_bounds(&brd, 0), (5, 6, 5, 6));
+/// assert_eq!(reachable_bounds(&brd, 3), (2, 9, 2, 9));
+/// assert_eq!(reachable_bounds(&brd, 8), (0, 11, 0, 11));
+/// assert_eq!(reachable_bounds(&Array2::zeros((3, 3)), 2), (0, 0, 0, 0));
+/// ```
+pub fn reachable_bounds(brd: &ndarray::Array2<u8>, generations: usize) -> (usize, usize, usize, usize) {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    return match bounding_box(brd) {
+        Some((ri, rf, ci, cf)) => (ri.saturating_sub(generations), (rf + generations).min(rows),
+                                   ci.saturating_sub(generations), (cf + generations).min(cols)),
+        None => (0, 0, 0, 0),
+    }
+}
+
+/// Crop a board to the bounding box of its live cells.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The cropped board, which is 0x0 if the board is empty.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::crop;
+///
+/// assert_eq!(crop(&array![[0, 0, 0], [0, 1, 0], [0, 0, 1]]), array![[1, 0], [0, 1]]);
+/// assert_eq!(crop(&array![[0, 0], [0, 0]]).dim(), (0, 0));
+/// ```
+pub fn crop(brd: &ndarray::Array2<u8>) -> ndarray::Array2<u8> {
+    return match bounding_box(brd) {
+        Some((ri, rf, ci, cf)) => brd.slice(ndarray::s![ri..rf, ci..cf]).to_owned(),
+        None => ndarray::Array2::zeros((0, 0)),
+    }
+}
+
+/// One of the 8 symmetries of a square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    /// Rotate a quarter turn clockwise.
+    Rotate90,
+    Rotate180,
+    /// Rotate a quarter turn counter-clockwise.
+    Rotate270,
+    /// Mirror left to right.
+    FlipHorizontal,
+    /// Mirror top to bottom.
+    FlipVertical,
+    /// Mirror across the main diagonal (transpose).
+    FlipDiagonal,
+    /// Mirror across the anti-diagonal.
+    FlipAntiDiagonal,
+}
+
+impl Symmetry {
+    /// Every symmetry, starting with the identity.
+    pub const ALL: [Symmetry; 8] = [Symmetry::Identity, Symmetry::Rotate90, Symmetry::Rotate180,
+                                    Symmetry::Rotate270, Symmetry::FlipHorizontal,
+                                    Symmetry::FlipVertical, Symmetry::FlipDiagonal,
+                                    Symmetry::FlipAntiDiagonal];
+}
+
+/// Apply a symmetry to a board.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `sym` - the symmetry.
+///
+/// # Returns
+/// The transformed board. Rotations by a quarter turn and diagonal flips swap the dimensions.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{transform, Symmetry};
+///
+/// let arr = array![[1, 2, 3],
+///                  [4, 5, 6]];
+/// assert_eq!(transform(&arr, Symmetry::Rotate90), array![[4, 1], [5, 2], [6, 3]]);
+/// assert_eq!(transform(&arr, Symmetry::Rotate270), array![[3, 6], [2, 5], [1, 4]]);
+/// assert_eq!(transform(&arr, Symmetry::FlipHorizontal), array![[3, 2, 1], [6, 5, 4]]);
+/// assert_eq!(transform(&arr, Symmetry::FlipAntiDiagonal), array![[6, 3], [5, 2], [4, 1]]);
+/// ```
+pub fn transform(brd: &ndarray::Array2<u8>, sym: Symmetry) -> ndarray::Array2<u8> {
+    use ndarray::s;
+    return match sym {
+        Symmetry::Identity => brd.to_owned(),
+        Symmetry::Rotate90 => brd.t().slice(s![.., ..;-1]).to_owned(),
+        Symmetry::Rotate180 => brd.slice(s![..;-1, ..;-1]).to_owned(),
+        Symmetry::Rotate270 => brd.t().slice(s![..;-1, ..]).to_owned(),
+        Symmetry::FlipHorizontal => brd.slice(s![.., ..;-1]).to_owned(),
+        Symmetry::FlipVertical => brd.slice(s![..;-1, ..]).to_owned(),
+        Symmetry::FlipDiagonal => brd.t().to_owned(),
+        Symmetry::FlipAntiDiagonal => brd.t().slice(s![..;-1, ..;-1]).to_owned(),
+    }
+}
+
+/// A set of symmetries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SymmetrySet {
+    bits: u8,
+}
+
+impl SymmetrySet {
+    /// Add a symmetry to the set.
+    pub fn insert(&mut self, sym: Symmetry) {
+        self.bits |= 1 << (sym as u8);
+    }
+
+    /// Whether the set contains a symmetry.
+    pub fn contains(&self, sym: Symmetry) -> bool {
+        return self.bits & (1 << (sym as u8)) != 0
+    }
+
+    /// The number of symmetries in the set.
+    pub fn len(&self) -> usize {
+        return self.bits.count_ones() as usize
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        return self.bits == 0
+    }
+
+    /// The symmetries in the set, in the order of `Symmetry::ALL`.
+    pub fn iter(&self) -> impl Iterator<Item = Symmetry> + '_ {
+        return Symmetry::ALL.iter().copied().filter(move |&sym| self.contains(sym))
+    }
+}
+
+/// Determine which symmetries of a square leave the (bounding-box-cropped) pattern unchanged.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The set of symmetries the pattern possesses. It always contains the identity.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{symmetries, Symmetry};
+///
+/// let block = array![[0, 0, 0, 0],
+///                    [0, 1, 1, 0],
+///                    [0, 1, 1, 0],
+///                    [0, 0, 0, 0]];
+/// assert_eq!(symmetries(&block).len(), 8);
+///
+/// let glider = array![[0, 1, 0],
+///                     [0, 0, 1],
+///                     [1, 1, 1]];
+/// let syms = symmetries(&glider);
+/// assert_eq!(syms.len(), 1);
+/// assert!(syms.contains(Symmetry::Identity));
+///
+/// let blinker = array![[0, 0, 0], [1, 1, 1], [0, 0, 0]];
+/// let syms = symmetries(&blinker);
+/// assert!(syms.contains(Symmetry::Rotate180) & syms.contains(Symmetry::FlipVertical));
+/// assert!(!syms.contains(Symmetry::Rotate90));
+/// ```
+pub fn symmetries(brd: &ndarray::Array2<u8>) -> SymmetrySet {
+    let cropped = crop(brd);
+    let mut set = SymmetrySet::default();
+    for &sym in Symmetry::ALL.iter() {
+        if transform(&cropped, sym) == cropped { set.insert(sym); }
+    }
+    return set
+}
+
+/// Render a board as a raw RGB24 frame, suitable for piping into a video encoder such as
+/// `ffmpeg -f rawvideo -pix_fmt rgb24 -s WxH -i - out.mp4`.
+///
+/// The frame is `cols * scale` pixels wide and `rows * scale` pixels high. Pixels are written
+/// row-major from the top left, three bytes (red, green, blue) each, with no header or padding.
+/// Live cells are white and dead cells are black.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `scale` - the side length of each cell in pixels.
+///
+/// # Returns
+/// The frame bytes, `rows * cols * scale * scale * 3` long.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::rgb_frame;
+///
+/// let frame = rgb_frame(&array![[1, 0]], 2);
+/// assert_eq!(frame.len(), 2 * 4 * 3);
+/// assert_eq!(&frame[..12], &[255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0]);
+/// assert_eq!(&frame[..12], &frame[12..]);
+/// ```
+pub fn rgb_frame(brd: &ndarray::Array2<u8>, scale: usize) -> Vec<u8> {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    let mut frame: Vec<u8> = Vec::with_capacity(rows * cols * scale * scale * 3);
+    for r in 0..rows {
+        for _ in 0..scale {
+            for c in 0..cols {
+                let v: u8 = if brd[[r, c]] == 1 { 255 } else { 0 };
+                for _ in 0..scale * 3 { frame.push(v); }
+            }
+        }
+    }
+    return frame
+}
+
+/// A cell-wise operation for combining two boards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombineOp {
+    /// Alive if alive on either board.
+    Or,
+    /// Alive if alive on both boards.
+    And,
+    /// Alive if alive on exactly one board.
+    Xor,
+}
+
+/// Combine two boards of the same shape cell by cell.
+///
+/// # Arguments
+/// `a` - the first board.
+/// `b` - the second board.
+/// `op` - the operation to apply to each pair of cells.
+///
+/// # Returns
+/// The combined board, or an error if the shapes differ.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{combine, CombineOp};
+///
+/// let a = array![[1, 1], [0, 0]];
+/// let b = array![[0, 1], [0, 1]];
+/// assert_eq!(combine(&a, &b, CombineOp::Or), Ok(array![[1, 1], [0, 1]]));
+/// assert_eq!(combine(&a, &b, CombineOp::And), Ok(array![[0, 1], [0, 0]]));
+/// assert_eq!(combine(&a, &b, CombineOp::Xor), Ok(array![[1, 0], [0, 1]]));
+/// assert!(combine(&a, &array![[1, 1, 1]], CombineOp::Or).is_err());
+/// ```
+pub fn combine(a: &ndarray::Array2<u8>,
+               b: &ndarray::Array2<u8>,
+               op: CombineOp) -> Result<ndarray::Array2<u8>, ShapeMismatch> {
+    check_shapes(a, b)?;
+    let mut ret: ndarray::Array2<u8> = ndarray::Array2::zeros(a.dim());
+    ndarray::Zip::from(&mut ret).and(a).and(b).apply(|v, &x, &y| {
+        let (x, y) = (x == 1, y == 1);
+        let alive = match op {
+            CombineOp::Or => x | y,
+            CombineOp::And => x & y,
+            CombineOp::Xor => x ^ y,
+        };
+        *v = alive as u8;
+    });
+    return Ok(ret)
+}
+
+/// A list of subgrids does not exactly tile a board.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtentError {
+    /// The subgrid (ri, rf, ci, cf) is inverted or reaches beyond the board.
+    OutOfBounds((usize, usize, usize, usize)),
+    /// The cell at (r, c) is covered by more than one subgrid.
+    Overlap(usize, usize),
+    /// The cell at (r, c) is not covered by any subgrid.
+    Gap(usize, usize),
+}
+
+impl fmt::Display for ExtentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExtentError::OutOfBounds((ri, rf, ci, cf)) =>
+                write!(f, "subgrid ({}, {}, {}, {}) does not fit on the board", ri, rf, ci, cf),
+            ExtentError::Overlap(r, c) => write!(f, "cell ({}, {}) is covered by several subgrids", r, c),
+            ExtentError::Gap(r, c) => write!(f, "cell ({}, {}) is not covered by any subgrid", r, c),
+        }
+    }
+}
+
+impl std::error::Error for ExtentError {}
+
+/// Check that a list of subgrids covers every cell of a board exactly once.
+///
+/// # Arguments
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `extents` - the subgrids, each as (ri, rf, ci, cf).
+///
+/// # Returns
+/// Nothing, or the first problem found.
+///
+/// ```
+/// use game_of_life::utils::{get_subgrids, validate_extents, ExtentError};
+///
+/// assert_eq!(validate_extents(15, 7, &get_subgrids(15, 7)), Ok(()));
+/// assert_eq!(validate_extents(2, 2, &[(0, 2, 0, 1), (0, 2, 0, 2)]), Err(ExtentError::Overlap(0, 0)));
+/// assert_eq!(validate_extents(2, 2, &[(0, 2, 0, 1)]), Err(ExtentError::Gap(0, 1)));
+/// assert_eq!(validate_extents(2, 2, &[(0, 3, 0, 2)]), Err(ExtentError::OutOfBounds((0, 3, 0, 2))));
+/// ```
+pub fn validate_extents(rows: usize,
+                        cols: usize,
+                        extents: &[(usize, usize, usize, usize)]) -> Result<(), ExtentError> {
+    let mut covered: ndarray::Array2<u8> = ndarray::Array2::zeros((rows, cols));
+    for &(ri, rf, ci, cf) in extents {
+        if (ri > rf) | (ci > cf) | (rf > rows) | (cf > cols) {
+            return Err(ExtentError::OutOfBounds((ri, rf, ci, cf)));
+        }
+        for (r, c) in iproduct!(ri..rf, ci..cf) {
+            if covered[[r, c]] == 1 { return Err(ExtentError::Overlap(r, c)); }
+            covered[[r, c]] = 1;
+        }
+    }
+    if let Some(((r, c), _)) = covered.indexed_iter().find(|(_, &v)| v == 0) {
+        return Err(ExtentError::Gap(r, c));
+    }
+    return Ok(())
+}
+
+/// Advance a board by one generation, stepping each of a user supplied list of subgrids in its own
+/// thread. The subgrids must tile the board without gaps or overlaps.
+///
+/// # Arguments
+/// `src` - the board.
+/// `extents` - the subgrids, each as (ri, rf, ci, cf).
+///
+/// # Returns
+/// The next generation, or an error if the subgrids do not tile the board.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::step_parallel_with_extents;
+///
+/// let brd = array![[0, 0, 0, 0],
+///                  [0, 1, 1, 1],
+///                  [0, 0, 0, 0]];
+/// // Split unevenly into a narrow left column and a wide right block.
+/// let next = step_parallel_with_extents(&brd, &[(0, 3, 0, 1), (0, 3, 1, 4)]).unwrap();
+/// assert_eq!(next, array![[0, 0, 1, 0],
+///                         [0, 0, 1, 0],
+///                         [0, 0, 1, 0]]);
+/// assert!(step_parallel_with_extents(&brd, &[(0, 3, 0, 2), (0, 3, 1, 4)]).is_err());
+/// ```
+pub fn step_parallel_with_extents(src: &ndarray::Array2<u8>,
+                                  extents: &[(usize, usize, usize, usize)])
+                                  -> Result<ndarray::Array2<u8>, ExtentError> {
+    let rows = src.shape()[0];
+    let cols = src.shape()[1];
+    validate_extents(rows, cols, extents)?;
+
+    let mut dst: ndarray::Array2<u8> = src.to_owned();
+    // Each thread reads the whole source, so it can see neighbors across subgrid boundaries,
+    // and returns the moves for its own subgrid.
+    let all_moves: Vec<Vec<(usize, usize, u8)>> = thread::scope(|scope| {
+        let handles: Vec<_> = extents.iter().map(|&(r0, rl, c0, cl)| {
+            scope.spawn(move || capture_moves(src, &rows, &cols, &r0, &rl, &c0, &cl))
+        }).collect();
+        return handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    for (r, c, v) in all_moves.into_iter().flatten() { dst[[r, c]] = v; }
+    return Ok(dst)
+}
+
+/// Advance a board by one generation, splitting it into bands of whole rows that are each stepped
+/// in their own thread. Each thread reads the rows around its band from the shared source and
+/// writes straight into its own rows of the result, so no moves are passed between threads.
+///
+/// # Arguments
+/// `src` - the board.
+/// `num_threads` - the number of bands. Fewer are used if the board has fewer rows.
+///
+/// # Returns
+/// The next generation, the same as `next_generation`.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::{next_generation, step_rows_parallel};
+///
+/// // Bands of 2 rows cut through the middle of the glider.
+/// let mut glider: Array2<u8> = Array2::zeros((8, 8));
+/// for &(r, c) in &[(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)] { glider[[r, c]] = 1; }
+/// let mut expected = glider.clone();
+/// for _ in 0..4 {
+///     glider = step_rows_parallel(&glider, 4);
+///     expected = next_generation(&expected);
+///     assert_eq!(glider, expected);
+/// }
+/// assert_eq!(step_rows_parallel(&expected, 100), next_generation(&expected));
+/// ```
+pub fn step_rows_parallel(src: &ndarray::Array2<u8>, num_threads: usize) -> ndarray::Array2<u8> {
+    let rows = src.shape()[0];
+    let cols = src.shape()[1];
+    let mut dst: ndarray::Array2<u8> = ndarray::Array2::zeros((rows, cols));
+    if rows == 0 { return dst; }
+    let band = rows.div_ceil(num_threads.clamp(1, rows));
+
+    thread::scope(|scope| {
+        for (i, mut rows_out) in dst.axis_chunks_iter_mut(ndarray::Axis(0), band).enumerate() {
+            scope.spawn(move || {
+                for ((dr, c), v) in rows_out.indexed_iter_mut() {
+                    let r = i * band + dr;
+                    let count = count_neighbors(src, &rows, &cols, &r, &c);
+                    *v = Rule::conway().next_state(src[[r, c]] == 1, count) as u8;
+                }
+            });
+        }
+    });
+    return dst
+}
+
+/// Advance a board by one generation on the current thread.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The next generation.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::next_generation;
+///
+/// let blinker = array![[0, 1, 0],
+///                      [0, 1, 0],
+///                      [0, 1, 0]];
+/// assert_eq!(next_generation(&blinker), array![[0, 0, 0], [1, 1, 1], [0, 0, 0]]);
+/// assert_eq!(next_generation(&next_generation(&blinker)), blinker);
+/// ```
+///
+/// Every other way of stepping a board with Conway's rules gives the same generation, on boards
+/// from 1x1 up to 64x64. The sizes and boards come from fixed seeds, so a failure names the case
+/// that reproduces it:
+///
+/// ```
+/// use std::sync::{Arc, RwLock};
+/// use ndarray::Array2;
+/// use rand::{Rng, SeedableRng};
+/// use game_of_life::{grid::{self, BitBoard, SparseBoard}, rule::{GenerationsRule, Rule}, utils::*};
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(184);
+/// let mut sizes: Vec<(usize, usize)> = vec![(1, 1), (1, 5), (5, 1), (2, 2), (64, 64)];
+/// for _ in 0..40 { sizes.push((rng.gen_range(1..=64), rng.gen_range(1..=64))); }
+///
+/// for (seed, &(rows, cols)) in sizes.iter().enumerate() {
+///     let density = [0.1, 0.35, 0.6][seed % 3];
+///     let brd: Array2<u8> = random_board(rows, cols, density, seed as u64);
+///     let expected = next_generation(&brd);
+///     let case = format!("case {}: {}x{} at density {}", seed, rows, cols, density);
+///
+///     assert_eq!(next_generation_with_rule(&brd, &Rule::conway()), expected, "{}", case);
+///     assert_eq!(step_padded(&brd), expected, "{}", case);
+///     assert_eq!(step_rows_parallel(&brd, 4), expected, "{}", case);
+///     assert_eq!(step(&brd, &StepConfig::default()), expected, "{}", case);
+///     assert_eq!(step_generations(&brd, &GenerationsRule::default()), expected, "{}", case);
+///     assert_eq!(step_region(&brd, 0, rows, 0, cols), expected, "{}", case);
+///     assert_eq!(advance(brd.clone(), 1), expected, "{}", case);
+///     assert_eq!(grid::step(&brd), expected, "{}", case);
+///     assert_eq!(Array2::from(&grid::step(&BitBoard::from(&brd))), expected, "{}", case);
+///     assert_eq!(Array2::from(&grid::step(&SparseBoard::from(&brd))), expected, "{}", case);
+///
+///     let shared = Arc::new(RwLock::new(brd.clone()));
+///     step_parallel(&shared, &rows, &cols, &get_subgrids(rows, cols), &Rule::conway());
+///     assert_eq!(*shared.read().unwrap(), expected, "{}", case);
+/// }
+/// ```
+pub fn next_generation<S: ndarray::Data<Elem = u8>>(brd: &ndarray::ArrayBase<S, ndarray::Ix2>) -> ndarray::Array2<u8> {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    let mut next: ndarray::Array2<u8> = brd.to_owned();
+    for (r, c, v) in capture_moves(brd, &rows, &cols, &0, &rows, &0, &cols) { next[[r, c]] = v; }
+    return next
+}
+
+/// Run the simulation on the current thread, keeping every generation.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `generations` - the number of generations to step.
+///
+/// # Returns
+/// The starting board followed by each of the `generations` that come after it.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::run;
+///
+/// let boards = run(&array![[0, 1, 0], [0, 1, 0], [0, 1, 0]], 2);
+/// assert_eq!(boards.len(), 3);
+/// assert_eq!(boards[2], boards[0]);
+/// ```
+pub fn run(initial: &ndarray::Array2<u8>, generations: usize) -> Vec<ndarray::Array2<u8>> {
+    let mut boards: Vec<ndarray::Array2<u8>> = vec![initial.to_owned()];
+    for _ in 0..generations {
+        let next = next_generation(boards.last().unwrap());
+        boards.push(next);
+    }
+    return boards
+}
+
+/// Jump ahead a number of generations on the current thread, keeping only the final board. Only two
+/// boards are ever allocated; each generation is written over the one before last.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `generations` - the number of generations to step.
+///
+/// # Returns
+/// The board after `generations` generations.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::{advance, next_generation, random_board};
+///
+/// let soup: Array2<u8> = random_board(20, 20, 0.4, 7);
+/// let mut expected = soup.clone();
+/// for _ in 0..25 { expected = next_generation(&expected); }
+/// assert_eq!(advance(soup.clone(), 25), expected);
+/// assert_eq!(advance(soup.clone(), 0), soup);
+/// ```
+pub fn advance(initial: ndarray::Array2<u8>, generations: usize) -> ndarray::Array2<u8> {
+    let rows = initial.shape()[0];
+    let cols = initial.shape()[1];
+    let mut front = initial;
+    let mut back: ndarray::Array2<u8> = ndarray::Array2::zeros((rows, cols));
+    for _ in 0..generations {
+        for (r, c) in iproduct!(0..rows, 0..cols) {
+            let count = count_neighbors(&front, &rows, &cols, &r, &c);
+            back[[r, c]] = Rule::conway().next_state(front[[r, c]] == 1, count) as u8;
+        }
+        std::mem::swap(&mut front, &mut back);
+    }
+    return front
+}
+
+/// Run the simulation and count how many generations each cell spends alive.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `generations` - the number of generations to step.
+///
+/// # Returns
+/// For each cell, the number of boards in the run (the starting board included) in which it is
+/// alive.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::activity_map;
+///
+/// let blinker = array![[0, 0, 0, 0, 0],
+///                      [0, 0, 1, 0, 0],
+///                      [0, 0, 1, 0, 0],
+///                      [0, 0, 1, 0, 0],
+///                      [0, 0, 0, 0, 0]];
+/// // Vertical, horizontal, vertical, horizontal, vertical.
+/// assert_eq!(activity_map(&blinker, 4), array![[0, 0, 0, 0, 0],
+///                                              [0, 0, 3, 0, 0],
+///                                              [0, 2, 5, 2, 0],
+///                                              [0, 0, 3, 0, 0],
+///                                              [0, 0, 0, 0, 0]]);
+/// ```
+pub fn activity_map(initial: &ndarray::Array2<u8>, generations: usize) -> ndarray::Array2<u32> {
+    let mut counts: ndarray::Array2<u32> = ndarray::Array2::zeros(initial.dim());
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for gen in 0..=generations {
+        ndarray::Zip::from(&mut counts).and(&brd).apply(|n, &v| *n += v as u32);
+        if gen < generations { brd = next_generation(&brd); }
+    }
+    return counts
+}
+
+/// Run the simulation and weight each cell by how recently it was alive: every generation the
+/// weights are multiplied by `decay`, and then live cells add 1. Regions that are active now
+/// stand out over those that were active long ago, such as the trail of a puffer.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `generations` - the number of generations to step.
+/// `decay` - how much of the weight is kept each generation. With 1, this is `activity_map`.
+///
+/// # Returns
+/// The weight of each cell after the last generation.
+///
+/// # Panics
+/// If `decay` is not in `(0, 1]`.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::decaying_activity;
+///
+/// // Each cell of a block tends to 1 / (1 - 0.9) = 10.
+/// let block = array![[0, 0, 0, 0], [0, 1, 1, 0], [0, 1, 1, 0], [0, 0, 0, 0]];
+/// let map = decaying_activity(&block, 200, 0.9);
+/// assert!((map[[1, 1]] - 10.0).abs() < 1e-6);
+/// assert_eq!(map[[0, 0]], 0.0);
+///
+/// // A cell that died long ago has faded.
+/// let map = decaying_activity(&array![[0, 0, 0], [0, 1, 0], [0, 0, 0]], 50, 0.5);
+/// assert!(map[[1, 1]] < 1e-12);
+/// assert_eq!(decaying_activity(&block, 3, 1.0)[[1, 1]], 4.0);
+/// ```
+pub fn decaying_activity(initial: &ndarray::Array2<u8>, generations: usize, decay: f64) -> ndarray::Array2<f64> {
+    assert!((decay > 0.0) & (decay <= 1.0), "decay must be in (0, 1], not {}", decay);
+    let mut weights: ndarray::Array2<f64> = ndarray::Array2::zeros(initial.dim());
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for gen in 0..=generations {
+        ndarray::Zip::from(&mut weights).and(&brd).apply(|w, &v| *w = *w * decay + v as f64);
+        if gen < generations { brd = next_generation(&brd); }
+    }
+    return weights
+}
+
+/// Load a board from an image file (PNG or BMP). The image is reduced to grayscale and every
+/// pixel darker than `threshold` becomes a live cell, one cell per pixel.
+///
+/// # Arguments
+/// `path` - the image file.
+/// `threshold` - the gray level (0 is black, 255 is white) below which a pixel is alive.
+///
+/// # Returns
+/// The board, or an error if the file cannot be read or is not a supported image.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::load_board_from_image;
+///
+/// let path = std::env::temp_dir().join("game_of_life_load_board_from_image.png");
+/// let img = image::GrayImage::from_raw(2, 2, vec![0, 255, 200, 10]).unwrap();
+/// img.save(&path).unwrap();
+///
+/// assert_eq!(load_board_from_image(&path, 128).unwrap(), array![[1, 0], [0, 1]]);
+/// assert!(load_board_from_image("does/not/exist.png", 128).is_err());
+///
+/// let not_an_image = std::env::temp_dir().join("game_of_life_not_an_image.png");
+/// std::fs::write(&not_an_image, "0,1\n1,0\n").unwrap();
+/// assert!(load_board_from_image(&not_an_image, 128).is_err());
+/// ```
+pub fn load_board_from_image<P: AsRef<std::path::Path>>(path: P, threshold: u8)
+                                                        -> Result<ndarray::Array2<u8>, image::ImageError> {
+    return load_board_from_image_downsampled(path, threshold, 1)
+}
+
+/// Load a board from an image drawn with several pixels per cell, such as a sketch. The image is
+/// reduced to grayscale and cut into squares of `downsample` pixels a side, and every square
+/// whose average gray is darker than `threshold` becomes a live cell. Pixels beyond the last
+/// whole square on the right or bottom are cropped off.
+///
+/// # Arguments
+/// `path` - the image file.
+/// `threshold` - the gray level (0 is black, 255 is white) below which a square is alive.
+/// `downsample` - the side length of each cell in pixels.
+///
+/// # Returns
+/// The board, or an error if the file cannot be read or is not a supported image.
+///
+/// # Panics
+/// If `downsample` is 0.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::load_board_from_image_downsampled;
+///
+/// let path = std::env::temp_dir().join("game_of_life_downsampled.png");
+/// let img = image::GrayImage::from_raw(4, 4, vec![  0,   0, 255, 255,
+///                                                   0, 255, 255,   0,
+///                                                 255, 255,   0,  40,
+///                                                 255, 200,  10,   0]).unwrap();
+/// img.save(&path).unwrap();
+/// assert_eq!(load_board_from_image_downsampled(&path, 128, 2).unwrap(), array![[1, 0], [0, 1]]);
+///
+/// // A 5x5 image holds only 2x2 whole squares of 2 pixels.
+/// let img = image::GrayImage::from_raw(5, 5, vec![0; 25]).unwrap();
+/// img.save(&path).unwrap();
+/// assert_eq!(load_board_from_image_downsampled(&path, 128, 2).unwrap(), array![[1, 1], [1, 1]]);
+/// ```
+pub fn load_board_from_image_downsampled<P: AsRef<std::path::Path>>(path: P, threshold: u8, downsample: usize)
+                                                                    -> Result<ndarray::Array2<u8>, image::ImageError> {
+    assert!(downsample > 0, "downsample must be at least 1");
+    let img = image::ImageReader::open(path)?.with_guessed_format()?.decode()?.to_luma8();
+    let rows = img.height() as usize / downsample;
+    let cols = img.width() as usize / downsample;
+    let mut sums: ndarray::Array2<usize> = ndarray::Array2::zeros((rows, cols));
+    for (c, r, px) in img.enumerate_pixels() {
+        if let Some(sum) = sums.get_mut([r as usize / downsample, c as usize / downsample]) { *sum += px.0[0] as usize; }
+    }
+    let area = downsample * downsample;
+    return Ok(sums.mapv(|sum| (sum < threshold as usize * area) as u8))
+}
+
+/// Detect still lifes and oscillators by remembering the last `window` boards of a run.
+///
+/// Memory use is `window` copies of the board.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{next_generation, CycleDetector};
+///
+/// let mut detector = CycleDetector::new(2);
+/// let mut brd = array![[0, 1, 0], [0, 1, 0], [0, 1, 0]];
+/// assert_eq!(detector.push(&brd), None);
+/// brd = next_generation(&brd);
+/// assert_eq!(detector.push(&brd), None);
+/// brd = next_generation(&brd);
+/// assert_eq!(detector.push(&brd), Some(2));
+///
+/// // A window of 1 only detects still lifes.
+/// let mut detector = CycleDetector::new(1);
+/// let block = array![[1, 1], [1, 1]];
+/// assert_eq!(detector.push(&block), None);
+/// assert_eq!(detector.push(&next_generation(&block)), Some(1));
+/// ```
+///
+/// A board that grows as it runs never equals an earlier one, but cropping each board to its
+/// bounding box still finds the cycle.
+///
+/// ```
+/// use ndarray::{s, Array2};
+/// use game_of_life::{pattern::TOAD, utils::{next_generation, CycleDetector}};
+///
+/// // Surround a board with a border of dead cells.
+/// let pad = |brd: &Array2<u8>, by: usize| {
+///     let mut padded: Array2<u8> = Array2::zeros((brd.nrows() + 2 * by, brd.ncols() + 2 * by));
+///     padded.slice_mut(s![by..by + brd.nrows(), by..by + brd.ncols()]).assign(brd);
+///     padded
+/// };
+/// let mut plain = CycleDetector::new(4);
+/// let mut cropped = CycleDetector::new(4).cropped();
+/// let mut brd = pad(&TOAD.board(), 1);
+/// let mut periods = (None, None);
+/// for _ in 0..4 {
+///     periods = (plain.push(&brd), cropped.push(&brd));
+///     brd = pad(&next_generation(&brd), 1);
+/// }
+/// assert_eq!(periods, (None, Some(2)));
+///
+/// // The position on the board is ignored too, so a glider repeats every 4 generations.
+/// let mut detector = CycleDetector::new(4).cropped();
+/// let mut brd = pad(&game_of_life::pattern::GLIDER.board(), 4);
+/// for _ in 0..4 {
+///     assert_eq!(detector.push(&brd), None);
+///     brd = next_generation(&brd);
+/// }
+/// assert_eq!(detector.push(&brd), Some(4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CycleDetector {
+    window: usize,
+    /// Whether boards are cropped to their bounding boxes before being compared.
+    cropped: bool,
+    history: std::collections::VecDeque<ndarray::Array2<u8>>,
+}
+
+impl CycleDetector {
+    /// Create a detector remembering the last `window` boards.
+    ///
+    /// # Panics
+    /// If `window` is 0.
+    pub fn new(window: usize) -> CycleDetector {
+        assert!(window >= 1, "the detection window must be at least 1");
+        return CycleDetector { window, cropped: false, history: std::collections::VecDeque::with_capacity(window) }
+    }
+
+    /// Compare boards cropped to their bounding boxes, ignoring the size of the board and where
+    /// on it the live cells are, for boards that are resized or padded as they run. Only the
+    /// cropped boards are remembered.
+    pub fn cropped(mut self) -> CycleDetector {
+        self.cropped = true;
+        return self
+    }
+
+    /// Record the next board of a run.
+    ///
+    /// # Arguments
+    /// `brd` - the board.
+    ///
+    /// # Returns
+    /// The period, if the board equals one of the remembered boards (1 for a still life).
+    pub fn push(&mut self, brd: &ndarray::Array2<u8>) -> Option<usize> {
+        // The most recent board is at the back, one generation ago.
+        let brd = if self.cropped { crop(brd) } else { brd.to_owned() };
+        let period = self.history.iter().rev().position(|old| *old == brd).map(|idx| idx + 1);
+        if self.history.len() == self.window { self.history.pop_front(); }
+        self.history.push_back(brd);
+        return period
+    }
+}
+
+/// Generate a random board, with the same board for the same seed.
+///
+/// # Arguments
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `density` - the probability that each cell is alive.
+/// `seed` - the seed for the random number generator.
+///
+/// # Returns
+/// The board.
+///
+/// # Panics
+/// If `density` is not between 0 and 1.
+///
+/// ```
+/// use game_of_life::utils::{population, random_board};
+///
+/// let brd = random_board(40, 30, 0.25, 7);
+/// assert_eq!(brd.dim(), (40, 30));
+/// assert_eq!(brd, random_board(40, 30, 0.25, 7));
+/// assert_ne!(brd, random_board(40, 30, 0.25, 8));
+/// assert_eq!(population(&random_board(5, 5, 1.0, 0)), 25);
+/// ```
+pub fn random_board(rows: usize, cols: usize, density: f64, seed: u64) -> ndarray::Array2<u8> {
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    return ndarray::Array2::from_shape_fn((rows, cols), |_| rng.gen_bool(density) as u8)
+}
+
+/// Where the cell at (r, c) of a `size` by `size` board ends up after applying a symmetry.
+fn transform_position(size: usize, sym: Symmetry, r: usize, c: usize) -> (usize, usize) {
+    let last = size - 1;
+    return match sym {
+        Symmetry::Identity => (r, c),
+        Symmetry::Rotate90 => (c, last - r),
+        Symmetry::Rotate180 => (last - r, last - c),
+        Symmetry::Rotate270 => (last - c, r),
+        Symmetry::FlipHorizontal => (r, last - c),
+        Symmetry::FlipVertical => (last - r, c),
+        Symmetry::FlipDiagonal => (c, r),
+        Symmetry::FlipAntiDiagonal => (last - c, last - r),
+    }
+}
+
+/// Generate a random square board that is unchanged by a given symmetry. A random board is
+/// generated first, then every cell takes the value of the first cell (in row-major order) that
+/// the symmetry maps onto it.
+///
+/// # Arguments
+/// `size` - the number of rows and columns.
+/// `density` - the probability that each cell of the random board is alive.
+/// `seed` - the seed for the random number generator.
+/// `symmetry` - the symmetry to enforce.
+///
+/// # Returns
+/// The board.
+///
+/// ```
+/// use game_of_life::utils::{random_soup_symmetric, symmetries, Symmetry};
+///
+/// for &sym in Symmetry::ALL.iter() {
+///     let brd = random_soup_symmetric(16, 0.5, 42, sym);
+///     assert!(symmetries(&brd).contains(sym));
+/// }
+/// ```
+pub fn random_soup_symmetric(size: usize,
+                             density: f64,
+                             seed: u64,
+                             symmetry: Symmetry) -> ndarray::Array2<u8> {
+    let mut brd = random_board(size, size, density, seed);
+    let mut visited: ndarray::Array2<bool> = ndarray::Array2::from_elem((size, size), false);
+    for (r, c) in iproduct!(0..size, 0..size) {
+        if visited[[r, c]] { continue; }
+        // Walk the orbit of this cell under the symmetry, copying its value along the way.
+        let v = brd[[r, c]];
+        let mut pos = (r, c);
+        while !visited[[pos.0, pos.1]] {
+            visited[[pos.0, pos.1]] = true;
+            brd[[pos.0, pos.1]] = v;
+            pos = transform_position(size, symmetry, pos.0, pos.1);
+        }
+    }
+    return brd
+}
+
+/// Run the simulation until a condition holds or a generation limit is reached.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `max_gens` - the most generations to step.
+/// `predicate` - called with each board and its generation, starting with the initial board at
+/// generation 0 and ending with the board at generation `max_gens`; the run stops as soon as it
+/// returns true.
+///
+/// # Returns
+/// The first board the predicate holds for and its generation, or the board at generation
+/// `max_gens` if there is none. At `max_gens`, whether the predicate holds for the returned board
+/// tells the two apart.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::{population, run_until};
+///
+/// // An R-pentomino grows past 10 cells after a few generations.
+/// let mut r_pentomino: Array2<u8> = Array2::zeros((20, 20));
+/// for &(r, c) in &[(9, 10), (9, 11), (10, 9), (10, 10), (11, 10)] { r_pentomino[[r, c]] = 1; }
+///
+/// let (brd, gen) = run_until(&r_pentomino, 100, |brd, _| population(brd) > 10);
+/// assert!(population(&brd) > 10);
+/// assert!((gen > 0) & (gen < 100));
+///
+/// let (_, gen) = run_until(&r_pentomino, 5, |_, _| false);
+/// assert_eq!(gen, 5);
+///
+/// // The board at the last generation is tested too.
+/// let (brd, gen) = run_until(&r_pentomino, 5, |_, gen| gen == 5);
+/// let (expected, _) = run_until(&r_pentomino, 5, |_, _| false);
+/// assert_eq!((brd, gen), (expected, 5));
+/// let calls = std::cell::Cell::new(0);
+/// run_until(&r_pentomino, 5, |_, _| { calls.set(calls.get() + 1); false });
+/// assert_eq!(calls.get(), 6);
+/// ```
+pub fn run_until<F>(initial: &ndarray::Array2<u8>,
+                    max_gens: usize,
+                    predicate: F) -> (ndarray::Array2<u8>, usize)
+    where F: Fn(&ndarray::Array2<u8>, usize) -> bool {
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    let mut gen: usize = 0;
+    while !predicate(&brd, gen) & (gen < max_gens) {
+        brd = next_generation(&brd);
+        gen += 1;
+    }
+    return (brd, gen)
+}
+
+/// Advance a board by one generation on the current thread, treating positions beyond the edge
+/// of the board according to a boundary policy.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `boundary` - how to treat neighbors outside of the board.
+///
+/// # Returns
+/// The next generation.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{next_generation, next_generation_with_boundary, Boundary};
+///
+/// // A blinker lying along the edge of a torus sees the far edge as its neighbor.
+/// let brd = array![[0, 0, 0, 0, 0],
+///                  [0, 0, 0, 0, 0],
+///                  [0, 0, 0, 0, 0],
+///                  [0, 0, 0, 0, 0],
+///                  [0, 1, 1, 1, 0]];
+/// assert_eq!(next_generation_with_boundary(&brd, Boundary::Torus), array![[0, 0, 1, 0, 0],
+///                                                                         [0, 0, 0, 0, 0],
+///                                                                         [0, 0, 0, 0, 0],
+///                                                                         [0, 0, 1, 0, 0],
+///                                                                         [0, 0, 1, 0, 0]]);
+/// assert_eq!(next_generation_with_boundary(&brd, Boundary::Fixed), next_generation(&brd));
+/// ```
+pub fn next_generation_with_boundary(brd: &ndarray::Array2<u8>, boundary: Boundary) -> ndarray::Array2<u8> {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    let rule = Rule::conway();
+    let mut next: ndarray::Array2<u8> = brd.to_owned();
+    for (r, c) in iproduct!(0..rows, 0..cols) {
+        let count = count_neighbors_with_boundary(brd, &rows, &cols, &r, &c, boundary);
+        next[[r, c]] = if brd[[r, c]] == 1 { rule.survives(count) as u8 } else { rule.born(count) as u8 };
+    }
+    return next
+}
+
+/// Shift every cell of a board, wrapping around at the edges.
+fn roll(brd: &ndarray::Array2<u8>, dr: i64, dc: i64) -> ndarray::Array2<u8> {
+    let rows = brd.shape()[0] as i64;
+    let cols = brd.shape()[1] as i64;
+    return ndarray::Array2::from_shape_fn(brd.dim(), |(r, c)| {
+        brd[[(r as i64 - dr).rem_euclid(rows) as usize, (c as i64 - dc).rem_euclid(cols) as usize]]
+    })
+}
+
+/// A pattern that reappears, shifted, after some number of generations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spaceship {
+    /// The number of generations until the pattern reappears.
+    pub period: usize,
+    /// The rows moved per period; positive is down.
+    pub dr: i64,
+    /// The columns moved per period; positive is right.
+    pub dc: i64,
+}
+
+/// Check whether one board holds the same live cells as another, shifted. The boards may be of
+/// different sizes.
+///
+/// # Arguments
+/// `a` - the first board.
+/// `b` - the second board.
+///
+/// # Returns
+/// The rows (positive is down) and columns (positive is right) that the live cells of `a` move to
+/// become those of `b`, or `None` if `b` is not a shifted copy of `a` or both are empty.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::is_translation;
+///
+/// let mut a: Array2<u8> = Array2::zeros((10, 10));
+/// for &(r, c) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] { a[[r, c]] = 1; }
+/// let mut b: Array2<u8> = Array2::zeros((8, 12));
+/// for &(r, c) in &[(4, 7), (5, 8), (6, 6), (6, 7), (6, 8)] { b[[r, c]] = 1; }
+/// assert_eq!(is_translation(&a, &b), Some((4, 6)));
+/// assert_eq!(is_translation(&b, &a), Some((-4, -6)));
+/// assert_eq!(is_translation(&a, &a), Some((0, 0)));
+///
+/// b[[6, 6]] = 0;
+/// b[[5, 6]] = 1;
+/// assert_eq!(is_translation(&a, &b), None);
+/// ```
+pub fn is_translation(a: &ndarray::Array2<u8>, b: &ndarray::Array2<u8>) -> Option<(i64, i64)> {
+    let (ar, _, ac, _) = bounding_box(a)?;
+    let (br, _, bc, _) = bounding_box(b)?;
+    if crop(a) != crop(b) { return None; }
+    return Some((br as i64 - ar as i64, bc as i64 - ac as i64))
+}
+
+/// Find the smallest shift that turns `a` into `b`, if any. On a torus the shift is taken modulo
+/// the board dimensions and reported as the shortest equivalent displacement.
+fn find_shift(a: &ndarray::Array2<u8>,
+              b: &ndarray::Array2<u8>,
+              boundary: Boundary) -> Option<(i64, i64)> {
+    if (a.shape() != b.shape()) | (population(a) == 0) | (population(a) != population(b)) { return None; }
+    if boundary != Boundary::Torus { return is_translation(a, b); }
+
+    if a == b { return Some((0, 0)); }
+    let rows = a.shape()[0] as i64;
+    let cols = a.shape()[1] as i64;
+    // Wrap a displacement into (-len / 2, len / 2].
+    let shortest = |d: i64, len: i64| { let d = d.rem_euclid(len); if d > len / 2 { d - len } else { d } };
+    // Any shift has to carry the first live cell of `a` onto a live cell of `b`.
+    let (first, _) = a.indexed_iter().find(|(_, &v)| v == 1)?;
+    for ((r, c), _) in b.indexed_iter().filter(|(_, &v)| v == 1) {
+        let dr = r as i64 - first.0 as i64;
+        let dc = c as i64 - first.1 as i64;
+        if roll(a, dr, dc) == *b { return Some((shortest(dr, rows), shortest(dc, cols))); }
+    }
+    return None
+}
+
+/// Detect whether a board holds a spaceship: a pattern that reappears shifted after some period.
+///
+/// On a `Boundary::Torus` board the pattern may wrap around the edges, and the displacement is
+/// computed modulo the board dimensions.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `max_period` - the longest period to look for.
+/// `boundary` - how to treat neighbors outside of the board.
+///
+/// # Returns
+/// The spaceship's period and displacement, or `None` if the board never reappears shifted.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::{detect_spaceship, Boundary, Spaceship};
+///
+/// // A glider in the bottom right corner of a small torus wraps around within one period.
+/// let mut glider: Array2<u8> = Array2::zeros((6, 6));
+/// for &(r, c) in &[(3, 4), (4, 5), (5, 3), (5, 4), (5, 5)] { glider[[r, c]] = 1; }
+/// assert_eq!(detect_spaceship(&glider, 8, Boundary::Torus), Some(Spaceship { period: 4, dr: 1, dc: 1 }));
+/// assert_eq!(detect_spaceship(&glider, 8, Boundary::Fixed), None);
+///
+/// // Away from the edges, the same glider is found without wrapping.
+/// let mut glider: Array2<u8> = Array2::zeros((10, 10));
+/// for &(r, c) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] { glider[[r, c]] = 1; }
+/// assert_eq!(detect_spaceship(&glider, 8, Boundary::Fixed), Some(Spaceship { period: 4, dr: 1, dc: 1 }));
+/// ```
+pub fn detect_spaceship(initial: &ndarray::Array2<u8>,
+                        max_period: usize,
+                        boundary: Boundary) -> Option<Spaceship> {
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for period in 1..=max_period {
+        brd = next_generation_with_boundary(&brd, boundary);
+        match find_shift(initial, &brd, boundary) {
+            Some((0, 0)) => return None,
+            Some((dr, dc)) => return Some(Spaceship { period, dr, dc }),
+            None => {}
+        }
+    }
+    return None
+}
+
+/// Write a population time series as a two-column CSV with a `generation,population` header.
+///
+/// # Arguments
+/// `series` - the population of each generation, starting with generation 0.
+/// `path` - the file to write.
+///
+/// # Returns
+/// Nothing, or an error if the file cannot be written.
+///
+/// ```
+/// use game_of_life::utils::save_population_series;
+///
+/// let path = std::env::temp_dir().join("game_of_life_population_series.csv");
+/// save_population_series(&[5, 3, 8], &path).unwrap();
+///
+/// let mut reader = csv::Reader::from_path(&path).unwrap();
+/// let rows: Vec<(usize, usize)> = reader.deserialize().map(|row| row.unwrap()).collect();
+/// assert_eq!(rows, vec![(0, 5), (1, 3), (2, 8)]);
+/// ```
+pub fn save_population_series<P: AsRef<std::path::Path>>(series: &[usize], path: P) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_path(path)?;
+    writer.write_record(["generation", "population"])?;
+    for (gen, pop) in series.iter().enumerate() {
+        writer.serialize((gen, pop))?;
+    }
+    writer.flush()?;
+    return Ok(())
+}
+
+/// Move every cell of a board by an offset, keeping the board the same size.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `dr` - the rows to move by; positive is down.
+/// `dc` - the columns to move by; positive is right.
+/// `wrap` - whether cells moved past an edge reappear at the opposite edge, rather than being
+/// dropped.
+///
+/// # Returns
+/// The shifted board.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::translate;
+///
+/// let arr = array![[1, 0, 0],
+///                  [0, 1, 0],
+///                  [0, 0, 1]];
+/// assert_eq!(translate(&arr, 1, 0, false), array![[0, 0, 0], [1, 0, 0], [0, 1, 0]]);
+/// assert_eq!(translate(&arr, 0, -2, false), array![[0, 0, 0], [0, 0, 0], [1, 0, 0]]);
+/// assert_eq!(translate(&arr, 0, -2, true), array![[0, 1, 0], [0, 0, 1], [1, 0, 0]]);
+/// ```
+pub fn translate(brd: &ndarray::Array2<u8>, dr: i64, dc: i64, wrap: bool) -> ndarray::Array2<u8> {
+    if wrap { return roll(brd, dr, dc); }
+    return ndarray::Array2::from_shape_fn(brd.dim(), |(r, c)| {
+        get(brd, r as i64 - dr, c as i64 - dc).unwrap_or(0)
+    })
+}
+
+/// Find every cell that would change in the next generation, without changing the board.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The moves over the whole board, as from `capture_moves`, keeping only those that change a
+/// cell.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::pending_changes;
+///
+/// let blinker = array![[0, 1, 0],
+///                      [0, 1, 0],
+///                      [0, 1, 0]];
+/// assert_eq!(pending_changes(&blinker), vec![(0, 1, 0), (1, 0, 1), (1, 2, 1), (2, 1, 0)]);
+/// ```
+pub fn pending_changes(brd: &ndarray::Array2<u8>) -> Vec<(usize, usize, u8)> {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    let mut moves = capture_moves(brd, &rows, &cols, &0, &rows, &0, &cols);
+    moves.retain(|&(r, c, v)| brd[[r, c]] != v);
+    return moves
+}
+
+/// A board could not be loaded from a CSV file.
+#[derive(Debug)]
+pub enum BoardLoadError {
+    /// The file could not be read or is not valid CSV.
+    Csv(csv::Error),
+    /// A cell is neither the alive nor the dead value of the encoding.
+    InvalidCell { row: usize, col: usize, value: String },
+    /// A row has a different number of cells than the first row.
+    Ragged { row: usize, expected: usize, found: usize },
+    /// The board has more cells than allowed.
+    TooLarge { cells: usize, limit: usize },
+    /// A line is longer than any row of a board within the limit could be, so it wasn't read.
+    LongRow { row: usize, limit: usize },
+}
+
+impl fmt::Display for BoardLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardLoadError::Csv(e) => write!(f, "cannot read board: {}", e),
+            BoardLoadError::InvalidCell { row, col, value } =>
+                write!(f, "invalid cell '{}' at row {}, column {}", value, row, col),
+            BoardLoadError::Ragged { row, expected, found } =>
+                write!(f, "row {} has {} cells, expected {}", row, found, expected),
+            BoardLoadError::TooLarge { cells, limit } =>
+                write!(f, "board has at least {} cells, more than the limit of {}", cells, limit),
+            BoardLoadError::LongRow { row, limit } =>
+                write!(f, "row {} is too long for a board of at most {} cells", row, limit),
+        }
+    }
+}
+
+impl std::error::Error for BoardLoadError {}
+
+impl From<csv::Error> for BoardLoadError {
+    fn from(e: csv::Error) -> BoardLoadError {
+        return BoardLoadError::Csv(e)
+    }
+}
+
+/// The values that stand for live and dead cells in a file, for tools that don't use 1 and 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Encoding {
+    pub alive: u8,
+    pub dead: u8,
+}
+
+impl Default for Encoding {
+    fn default() -> Encoding {
+        return Encoding { alive: 1, dead: 0 }
+    }
+}
+
+/// The most bytes a cell and its separator may take in a CSV file loaded with a limit, enough for
+/// a value padded with spaces.
+pub static MAX_BYTES_PER_CELL: usize = 16;
+
+/// Reads from a file, failing when a line grows past a number of bytes, so a huge line is refused
+/// before it is read into memory.
+struct CappedLines<R> {
+    inner: R,
+    cap: usize,
+    /// The lines before the current one.
+    lines: usize,
+    /// The bytes of the current line so far.
+    line: usize,
+    exceeded: bool,
+}
+
+impl<R: std::io::Read> std::io::Read for CappedLines<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        for &byte in &buf[..n] {
+            if byte == b'\n' { self.lines += 1; self.line = 0; } else { self.line += 1; }
+            if self.line > self.cap {
+                self.exceeded = true;
+                return Err(std::io::Error::other("line is too long"));
+            }
+        }
+        return Ok(n)
+    }
+}
+
+/// Options for loading a board from a CSV file.
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// The most cells the board may have. The limit is checked while the file is read, so an
+    /// oversized board is rejected before it is allocated. A line is refused once it is longer
+    /// than `max_cells` cells of `MAX_BYTES_PER_CELL` bytes each, before the rest of it is read.
+    /// `None` means no limit.
+    pub max_cells: Option<usize>,
+    /// The values of live and dead cells in the file.
+    pub encoding: Encoding,
+}
+
+/// Load a board from a CSV file with one row of cells per line and no header.
+///
+/// # Arguments
+/// `path` - the file to read.
+///
+/// # Returns
+/// The board, or an error if the file cannot be read or is not a board.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::load_board;
+///
+/// let path = std::env::temp_dir().join("game_of_life_load_board.csv");
+/// std::fs::write(&path, "0,1,0\n1,1,1\n").unwrap();
+/// assert_eq!(load_board(&path).unwrap(), array![[0, 1, 0], [1, 1, 1]]);
+/// ```
+pub fn load_board<P: AsRef<std::path::Path>>(path: P) -> Result<ndarray::Array2<u8>, BoardLoadError> {
+    return load_board_with_options(path, &LoadOptions::default())
+}
+
+/// Load a board from a CSV file with one row of cells per line and no header.
+///
+/// # Arguments
+/// `path` - the file to read.
+/// `options` - limits on the board, and how its cells are written.
+///
+/// # Returns
+/// The board, or an error if the file cannot be read, is not a board, or breaks a limit.
+///
+/// ```
+/// use game_of_life::utils::{load_board_with_options, BoardLoadError, LoadOptions};
+///
+/// let path = std::env::temp_dir().join("game_of_life_load_board_with_options.csv");
+/// std::fs::write(&path, "0,1,0\n1,1,1\n0,0,0\n").unwrap();
+///
+/// let options = LoadOptions { max_cells: Some(9), ..Default::default() };
+/// assert_eq!(load_board_with_options(&path, &options).unwrap().dim(), (3, 3));
+///
+/// let options = LoadOptions { max_cells: Some(8), ..Default::default() };
+/// match load_board_with_options(&path, &options) {
+///     Err(BoardLoadError::TooLarge { cells, limit }) => assert_eq!((cells, limit), (9, 8)),
+///     other => panic!("unexpected {:?}", other),
+/// }
+///
+/// // A single huge row is refused without reading all of it.
+/// std::fs::write(&path, format!("0,0\n{}", vec!["0"; 100_000].join(","))).unwrap();
+/// match load_board_with_options(&path, &options) {
+///     Err(BoardLoadError::LongRow { row, limit }) => assert_eq!((row, limit), (1, 8)),
+///     other => panic!("unexpected {:?}", other),
+/// }
+///
+/// std::fs::write(&path, "0,1,0\n1,1\n").unwrap();
+/// assert!(load_board_with_options(&path, &LoadOptions::default()).is_err());
+/// ```
+pub fn load_board_with_options<P: AsRef<std::path::Path>>(path: P,
+                                                          options: &LoadOptions)
+                                                          -> Result<ndarray::Array2<u8>, BoardLoadError> {
+    let file = std::fs::File::open(path).map_err(csv::Error::from)?;
+    let cap = options.max_cells.map_or(usize::MAX, |limit| limit.saturating_mul(MAX_BYTES_PER_CELL));
+    let capped = CappedLines { inner: file, cap, lines: 0, line: 0, exceeded: false };
+    let mut reader = csv::ReaderBuilder::new().has_headers(false).flexible(true).from_reader(capped);
+    let mut cells: Vec<u8> = vec![];
+    let mut rows: usize = 0;
+    let mut cols: usize = 0;
+    let mut record = csv::StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            // The reader reads ahead, so the long line may come after the rows read so far.
+            Err(_) if reader.get_ref().exceeded => {
+                return Err(BoardLoadError::LongRow { row: reader.get_ref().lines, limit: options.max_cells.unwrap_or(0) });
+            }
+            Err(e) => return Err(e.into()),
+        }
+        if rows == 0 { cols = record.len(); }
+        else if record.len() != cols {
+            return Err(BoardLoadError::Ragged { row: rows, expected: cols, found: record.len() });
+        }
+        rows += 1;
+        // Check the size before growing the buffer for this row.
+        if let Some(limit) = options.max_cells {
+            if rows * cols > limit { return Err(BoardLoadError::TooLarge { cells: rows * cols, limit }); }
+        }
+        for (col, field) in record.iter().enumerate() {
+            let invalid = || BoardLoadError::InvalidCell { row: rows - 1, col, value: field.to_string() };
+            let v: u8 = field.trim().parse().map_err(|_| invalid())?;
+            if v == options.encoding.alive { cells.push(1); }
+            else if v == options.encoding.dead { cells.push(0); }
+            else { return Err(invalid()); }
+        }
+    }
+    return Ok(ndarray::Array2::from_shape_vec((rows, cols), cells).unwrap())
+}
+
+/// Save a board to a CSV file with one row of cells per line and no header, which `load_board`
+/// reads back.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `path` - the file to write.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{load_board, save_board};
+///
+/// let path = std::env::temp_dir().join("game_of_life_save_board.csv");
+/// let brd = array![[0, 1, 0], [1, 1, 1]];
+/// save_board(&brd, &path).unwrap();
+/// assert_eq!(std::fs::read_to_string(&path).unwrap(), "0,1,0\n1,1,1\n");
+/// assert_eq!(load_board(&path).unwrap(), brd);
+/// ```
+pub fn save_board<P: AsRef<std::path::Path>>(brd: &ndarray::Array2<u8>, path: P) -> Result<(), csv::Error> {
+    return save_board_with_encoding(brd, path, Encoding::default())
+}
+
+/// Save a board to a CSV file, writing live and dead cells with the values of an encoding.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `path` - the file to write.
+/// `encoding` - the values to write for live and dead cells.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{load_board_with_options, save_board_with_encoding, Encoding, LoadOptions};
+///
+/// let path = std::env::temp_dir().join("game_of_life_save_board_with_encoding.csv");
+/// let image_like = Encoding { alive: 255, dead: 0 };
+/// let brd = array![[0, 1], [1, 1]];
+/// save_board_with_encoding(&brd, &path, image_like).unwrap();
+/// assert_eq!(std::fs::read_to_string(&path).unwrap(), "0,255\n255,255\n");
+///
+/// let options = LoadOptions { encoding: image_like, ..Default::default() };
+/// assert_eq!(load_board_with_options(&path, &options).unwrap(), brd);
+/// // The plain encoding doesn't know what 255 means.
+/// assert!(load_board_with_options(&path, &LoadOptions::default()).is_err());
+/// ```
+pub fn save_board_with_encoding<P: AsRef<std::path::Path>>(brd: &ndarray::Array2<u8>,
+                                                           path: P,
+                                                           encoding: Encoding) -> Result<(), csv::Error> {
+    return write_board(&mut csv::Writer::from_path(path)?, brd, encoding)
+}
+
+/// Write the rows of a board as CSV records.
+fn write_board<W: std::io::Write>(writer: &mut csv::Writer<W>,
+                                  brd: &ndarray::Array2<u8>,
+                                  encoding: Encoding) -> Result<(), csv::Error> {
+    for row in brd.genrows() {
+        writer.write_record(row.iter().map(|&v| {
+            (if v == 1 { encoding.alive } else { encoding.dead }).to_string()
+        }))?;
+    }
+    writer.flush()?;
+    return Ok(())
+}
+
+/// A run could not be saved as a zip archive.
+#[derive(Debug)]
+pub enum RunZipError {
+    /// A board could not be written as CSV.
+    Csv(csv::Error),
+    /// The archive could not be created or written.
+    Zip(zip::result::ZipError),
+}
+
+impl fmt::Display for RunZipError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunZipError::Csv(e) => write!(f, "cannot write board: {}", e),
+            RunZipError::Zip(e) => write!(f, "cannot write archive: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RunZipError {}
+
+impl From<csv::Error> for RunZipError {
+    fn from(e: csv::Error) -> RunZipError {
+        return RunZipError::Csv(e)
+    }
+}
+
+impl From<zip::result::ZipError> for RunZipError {
+    fn from(e: zip::result::ZipError) -> RunZipError {
+        return RunZipError::Zip(e)
+    }
+}
+
+impl From<std::io::Error> for RunZipError {
+    fn from(e: std::io::Error) -> RunZipError {
+        return RunZipError::Zip(zip::result::ZipError::Io(e))
+    }
+}
+
+/// Save a whole run as a single zip archive, one CSV file per generation in the format of
+/// `save_board`, named `gen_00000.csv`, `gen_00001.csv` and so on. The files are stored without
+/// compression.
+///
+/// # Arguments
+/// `boards` - the generations of the run, in order.
+/// `path` - the archive to write.
+///
+/// # Returns
+/// Nothing, or an error if a board cannot be written as CSV or the archive cannot be written.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{next_generation, save_run_zip, RunZipError};
+///
+/// let blinker = array![[0, 1, 0], [0, 1, 0], [0, 1, 0]];
+/// let path = std::env::temp_dir().join("game_of_life_run.zip");
+/// save_run_zip(&[blinker.clone(), next_generation(&blinker)], &path).unwrap();
+///
+/// let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+/// assert_eq!(archive.file_names().collect::<Vec<_>>().len(), 2);
+/// let mut text = String::new();
+/// std::io::Read::read_to_string(&mut archive.by_name("gen_00001.csv").unwrap(), &mut text).unwrap();
+/// assert_eq!(text, "0,0,0\n1,1,1\n0,0,0\n");
+/// assert!(archive.by_name("gen_00000.csv").is_ok());
+///
+/// let missing = std::env::temp_dir().join("game_of_life_no_such_dir").join("run.zip");
+/// assert!(matches!(save_run_zip(&[blinker], &missing), Err(RunZipError::Zip(_))));
+/// ```
+pub fn save_run_zip<P: AsRef<std::path::Path>>(boards: &[ndarray::Array2<u8>], path: P) -> Result<(), RunZipError> {
+    use std::io::Write;
+
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let mut archive = zip::ZipWriter::new(std::fs::File::create(path)?);
+    for (gen, brd) in boards.iter().enumerate() {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        write_board(&mut writer, brd, Encoding::default())?;
+        let data = writer.into_inner().map_err(|e| csv::Error::from(e.into_error()))?;
+        archive.start_file(format!("gen_{:05}.csv", gen), options)?;
+        archive.write_all(&data)?;
+    }
+    archive.finish()?;
+    return Ok(())
+}
+
+/// Advance a board by one generation without blocking the async runtime, by running the
+/// CPU-bound stepping on tokio's blocking thread pool. Requires the `async` feature.
+///
+/// # Arguments
+/// `board` - the board.
+///
+/// # Returns
+/// The next generation, the same as `next_generation`, or an error if the blocking task was
+/// cancelled or panicked.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{next_generation, step_async};
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let blinker = array![[0, 1, 0], [0, 1, 0], [0, 1, 0]];
+/// let next = runtime.block_on(step_async(blinker.clone())).unwrap();
+/// assert_eq!(next, next_generation(&blinker));
+/// ```
+#[cfg(feature = "async")]
+pub async fn step_async(board: ndarray::Array2<u8>) -> Result<ndarray::Array2<u8>, tokio::task::JoinError> {
+    return tokio::task::spawn_blocking(move || next_generation(&board)).await
+}
+
+/// Advance a one-dimensional (elementary) cellular automaton by one step. Each cell looks at itself
+/// and its left and right neighbors; the three cells, read as a binary number with the left cell
+/// as the high bit, pick the bit of `rule` giving the next state. Cells beyond the ends are dead.
+///
+/// # Arguments
+/// `row` - the cells.
+/// `rule` - the Wolfram rule number.
+///
+/// # Returns
+/// The next row.
+///
+/// ```
+/// use game_of_life::utils::step_elementary;
+///
+/// assert_eq!(step_elementary(&[0, 0, 1, 0, 0], 90), vec![0, 1, 0, 1, 0]);
+/// assert_eq!(step_elementary(&[0, 0, 1, 0, 0], 30), vec![0, 1, 1, 1, 0]);
+/// ```
+pub fn step_elementary(row: &[u8], rule: u8) -> Vec<u8> {
+    let at = |i: i64| -> u8 { if (i < 0) | (i >= row.len() as i64) { 0 } else { row[i as usize] } };
+    return (0..row.len() as i64).map(|i| {
+        let idx = (at(i - 1) << 2) | (at(i) << 1) | at(i + 1);
+        (rule >> idx) & 1
+    }).collect()
+}
+
+/// Run a one-dimensional (elementary) cellular automaton and stack the rows into a space-time
+/// diagram, one row per time step.
+///
+/// # Arguments
+/// `initial` - the starting row.
+/// `rule` - the Wolfram rule number.
+/// `steps` - the number of steps.
+///
+/// # Returns
+/// A board with `steps + 1` rows, starting with `initial`.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::elementary_spacetime;
+///
+/// // Rule 90 grows a Sierpinski triangle from a single live cell.
+/// assert_eq!(elementary_spacetime(&[0, 0, 0, 1, 0, 0, 0], 90, 3), array![[0, 0, 0, 1, 0, 0, 0],
+///                                                                        [0, 0, 1, 0, 1, 0, 0],
+///                                                                        [0, 1, 0, 0, 0, 1, 0],
+///                                                                        [1, 0, 1, 0, 1, 0, 1]]);
+/// ```
+pub fn elementary_spacetime(initial: &[u8], rule: u8, steps: usize) -> ndarray::Array2<u8> {
+    let mut diagram: ndarray::Array2<u8> = ndarray::Array2::zeros((steps + 1, initial.len()));
+    let mut row: Vec<u8> = initial.to_vec();
+    for t in 0..=steps {
+        for (c, &v) in row.iter().enumerate() { diagram[[t, c]] = v; }
+        row = step_elementary(&row, rule);
+    }
+    return diagram
+}
+
+/// Fill a board by repeating a pattern, cutting it off wherever it meets the edge of the board.
+///
+/// # Arguments
+/// `pattern` - the unit cell to repeat.
+/// `rows` - the number of rows of the board.
+/// `cols` - the number of columns of the board.
+///
+/// # Returns
+/// The board. It is all dead if the pattern is empty.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::tile;
+///
+/// assert_eq!(tile(&array![[1, 0], [0, 1]], 4, 4), array![[1, 0, 1, 0],
+///                                                        [0, 1, 0, 1],
+///                                                        [1, 0, 1, 0],
+///                                                        [0, 1, 0, 1]]);
+/// assert_eq!(tile(&array![[1, 1, 0]], 2, 4), array![[1, 1, 0, 1], [1, 1, 0, 1]]);
+/// ```
+pub fn tile(pattern: &ndarray::Array2<u8>, rows: usize, cols: usize) -> ndarray::Array2<u8> {
+    let (pr, pc) = pattern.dim();
+    if (pr == 0) | (pc == 0) { return ndarray::Array2::zeros((rows, cols)); }
+    return ndarray::Array2::from_shape_fn((rows, cols), |(r, c)| pattern[[r % pr, c % pc]])
+}
+
+/// List the coordinates of every live cell.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The (row, column) of each live cell, in row-major order.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::live_cells;
+///
+/// assert_eq!(live_cells(&array![[0, 1, 0], [1, 0, 1]]), vec![(0, 1), (1, 0), (1, 2)]);
+/// ```
+pub fn live_cells(brd: &ndarray::Array2<u8>) -> Vec<(usize, usize)> {
+    return brd.indexed_iter().filter(|(_, &v)| v == 1).map(|(pos, _)| pos).collect()
+}
+
+/// Find the period of a board by stepping it and comparing every generation to the starting board
+/// in full. Unlike hashing, this can't be fooled by collisions.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `max_period` - the longest period to look for.
+///
+/// # Returns
+/// The first generation at which the board exactly equals the starting board (1 for a still
+/// life), or `None` if that doesn't happen within `max_period` generations.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use game_of_life::utils::exact_period;
+///
+/// assert_eq!(exact_period(&array![[0, 1, 0], [0, 1, 0], [0, 1, 0]], 10), Some(2));
+/// assert_eq!(exact_period(&array![[1, 1], [1, 1]], 10), Some(1));
+///
+/// let mut glider: Array2<u8> = Array2::zeros((8, 8));
+/// for &(r, c) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] { glider[[r, c]] = 1; }
+/// assert_eq!(exact_period(&glider, 40), None);
+/// ```
+pub fn exact_period(initial: &ndarray::Array2<u8>, max_period: usize) -> Option<usize> {
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for period in 1..=max_period {
+        brd = next_generation(&brd);
+        if brd == *initial { return Some(period); }
+    }
+    return None
+}
+
+/// Render several boards side by side, in the text format of `print_board` without the header.
+/// Boards are aligned along their top edge, and shorter boards are padded with blank lines.
+///
+/// # Arguments
+/// `boards` - the boards, from left to right.
+/// `separator` - the text placed between neighboring boards on each line.
+///
+/// # Returns
+/// The rendered lines, each ending in a newline.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::render_side_by_side;
+///
+/// let a = array![[1, 0], [0, 1]];
+/// let b = array![[1, 1, 1]];
+/// assert_eq!(render_side_by_side(&[&a, &b], "| "), "1 0 | 1 1 1 \n0 1 |       \n");
+/// ```
+pub fn render_side_by_side(boards: &[&ndarray::Array2<u8>], separator: &str) -> String {
+    let height = boards.iter().map(|brd| brd.shape()[0]).max().unwrap_or(0);
+    let mut out = String::new();
+    for r in 0..height {
+        for (i, brd) in boards.iter().enumerate() {
+            if i > 0 { out.push_str(separator); }
+            for c in 0..brd.shape()[1] {
+                if r < brd.shape()[0] { out.push_str(&format!("{} ", brd[[r, c]])); }
+                else { out.push_str("  "); }
+            }
+        }
+        out.push('\n');
+    }
+    return out
+}
+
+/// Measure how disordered a board is, as the Shannon entropy (in bits) of the distribution of
+/// `block` by `block` cell configurations seen in every window position across the board.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `block` - the side length of the windows.
+///
+/// # Returns
+/// The entropy, from 0 for a uniform board up to `block * block` bits. It is 0 if the board is
+/// smaller than a window.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::{block_entropy, random_board, tile};
+///
+/// assert_eq!(block_entropy(&Array2::zeros((10, 10)), 2), 0.0);
+///
+/// // A checkerboard only has two 2x2 configurations, seen about equally often.
+/// let checker = tile(&ndarray::array![[1, 0], [0, 1]], 10, 10);
+/// assert!((block_entropy(&checker, 2) - 1.0).abs() < 1e-3);
+///
+/// assert!(block_entropy(&random_board(40, 40, 0.5, 1), 2) > 3.5);
+/// ```
+pub fn block_entropy(brd: &ndarray::Array2<u8>, block: usize) -> f64 {
+    let (rows, cols) = brd.dim();
+    if (block == 0) | (block > rows) | (block > cols) { return 0.0; }
+    let mut counts: std::collections::HashMap<Vec<u8>, usize> = std::collections::HashMap::new();
+    for (r, c) in iproduct!(0..=rows - block, 0..=cols - block) {
+        let window: Vec<u8> = brd.slice(ndarray::s![r..r + block, c..c + block]).iter().copied().collect();
+        *counts.entry(window).or_insert(0) += 1;
+    }
+    let total = ((rows - block + 1) * (cols - block + 1)) as f64;
+    return counts.values().map(|&n| { let p = n as f64 / total; -p * p.log2() }).sum::<f64>().abs()
+}
+
+/// Advance only a rectangular region of a board by one generation, leaving the rest frozen. Cells
+/// in the region still see their neighbors outside of it.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `r0` - the row the region starts on.
+/// `r1` - the row the region stops on.
+/// `c0` - the column the region starts on.
+/// `c1` - the column the region stops on.
+///
+/// # Returns
+/// The whole board, with the region stepped and everything else copied unchanged.
+///
+/// # Panics
+/// If the region reaches beyond the board.
+///
+/// ```
+/// use ndarray::{array, s};
+/// use game_of_life::utils::step_region;
+///
+/// let brd = array![[0, 1, 0, 0, 1],
+///                  [0, 1, 0, 0, 1],
+///                  [0, 1, 0, 0, 1]];
+/// let next = step_region(&brd, 0, 3, 0, 3);
+/// // The left blinker turns; the right one is frozen.
+/// assert_eq!(next, array![[0, 0, 0, 0, 1],
+///                         [1, 1, 1, 0, 1],
+///                         [0, 0, 0, 0, 1]]);
+/// assert_eq!(next.slice(s![.., 3..]), brd.slice(s![.., 3..]));
+/// ```
+pub fn step_region(brd: &ndarray::Array2<u8>,
+                   r0: usize,
+                   r1: usize,
+                   c0: usize,
+                   c1: usize) -> ndarray::Array2<u8> {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    assert!((r0 <= r1) & (r1 <= rows) & (c0 <= c1) & (c1 <= cols), "region is outside of the board");
+    let mut next: ndarray::Array2<u8> = brd.to_owned();
+    for (r, c, v) in capture_moves(brd, &rows, &cols, &r0, &r1, &c0, &c1) { next[[r, c]] = v; }
+    return next
+}
+
+/// Run the simulation and record the size of the live region's bounding box each generation, to
+/// tell bounded patterns from growing ones.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `generations` - the number of generations to step.
+///
+/// # Returns
+/// The (height, width) of the bounding box for the starting board and each generation after it,
+/// or (0, 0) when the board is empty.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use game_of_life::utils::extent_series;
+///
+/// let block = array![[0, 0, 0, 0], [0, 1, 1, 0], [0, 1, 1, 0], [0, 0, 0, 0]];
+/// assert_eq!(extent_series(&block, 3), vec![(2, 2); 4]);
+///
+/// let blinker = array![[0, 1, 0], [0, 1, 0], [0, 1, 0]];
+/// assert_eq!(extent_series(&blinker, 2), vec![(3, 1), (1, 3), (3, 1)]);
+///
+/// assert_eq!(extent_series(&Array2::zeros((3, 3)), 1), vec![(0, 0), (0, 0)]);
+/// ```
+pub fn extent_series(initial: &ndarray::Array2<u8>, generations: usize) -> Vec<(usize, usize)> {
+    let mut series: Vec<(usize, usize)> = vec![];
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for gen in 0..=generations {
+        series.push(match bounding_box(&brd) {
+            Some((ri, rf, ci, cf)) => (rf - ri, cf - ci),
+            None => (0, 0),
+        });
+        if gen < generations { brd = next_generation(&brd); }
+    }
+    return series
+}
+
+/// Run the simulation and record the population each generation, as `save_population_series`
+/// writes it.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `generations` - the number of generations to step.
+///
+/// # Returns
+/// The population of the starting board and each generation after it.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::population_series;
+///
+/// let corner = array![[1, 1, 0], [1, 0, 0], [0, 0, 0]];
+/// assert_eq!(population_series(&corner, 2), vec![3, 4, 4]);
+/// ```
+pub fn population_series(initial: &ndarray::Array2<u8>, generations: usize) -> Vec<usize> {
+    let mut series: Vec<usize> = vec![];
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for gen in 0..=generations {
+        series.push(population(&brd));
+        if gen < generations { brd = next_generation(&brd); }
+    }
+    return series
+}
+
+/// Run the simulation and record how much the population changes each generation, to show when a
+/// pattern turns from growing to shrinking.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `generations` - the number of generations to step.
+///
+/// # Returns
+/// For each step `t`, the population of generation `t + 1` less that of generation `t`.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::population_delta_series;
+///
+/// // A blinker keeps its 3 cells as it turns.
+/// let blinker = array![[0, 1, 0], [0, 1, 0], [0, 1, 0]];
+/// assert_eq!(population_delta_series(&blinker, 4), vec![0; 4]);
+///
+/// // Three cells of a block fill in the fourth and grow no further.
+/// let corner = array![[1, 1, 0], [1, 0, 0], [0, 0, 0]];
+/// assert_eq!(population_delta_series(&corner, 2), vec![1, 0]);
+///
+/// // A lone cell dies.
+/// assert_eq!(population_delta_series(&array![[0, 0], [0, 1]], 2), vec![-1, 0]);
+/// ```
+pub fn population_delta_series(initial: &ndarray::Array2<u8>, generations: usize) -> Vec<i64> {
+    let series = population_series(initial, generations);
+    return series.windows(2).map(|pair| pair[1] as i64 - pair[0] as i64).collect()
+}
+
+/// The longest oscillator period looked for when deciding whether a board has stabilized.
+pub static STABILIZATION_WINDOW: usize = 30;
+
+/// Run the simulation until it becomes a still life or an oscillator with a period up to
+/// `STABILIZATION_WINDOW`.
+///
+/// # Returns
+/// The first generation of the repeating cycle and its period, or `None` if that doesn't happen
+/// within `max_gens` generations.
+fn find_stabilization(initial: &ndarray::Array2<u8>, max_gens: usize) -> Option<(usize, usize)> {
+    let mut detector = CycleDetector::new(STABILIZATION_WINDOW);
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    detector.push(&brd);
+    for gen in 1..=max_gens {
+        brd = next_generation(&brd);
+        // If generation `gen - period` were not the first of the cycle, the repeat would have been
+        // seen a generation earlier.
+        if let Some(period) = detector.push(&brd) { return Some((gen - period, period)); }
+    }
+    return None
+}
+
+/// Run a batch of random square soups and record when each one stabilizes. The soups are run in
+/// parallel, and the results only depend on the seeds.
+///
+/// # Arguments
+/// `size` - the number of rows and columns of each soup.
+/// `density` - the probability that each cell of a soup starts alive.
+/// `seeds` - the seed of each soup, as for `random_board`.
+/// `max_gens` - the most generations to run each soup.
+///
+/// # Returns
+/// For each seed, in order, the first generation at which the soup is a still life or an
+/// oscillator (with a period up to `STABILIZATION_WINDOW`), or `None` if it isn't by `max_gens`.
+///
+/// ```
+/// use game_of_life::utils::stabilization_histogram;
+///
+/// let seeds = [1, 2, 3, 4];
+/// let results = stabilization_histogram(12, 0.4, &seeds, 500);
+/// assert_eq!(results.len(), 4);
+/// assert_eq!(results, stabilization_histogram(12, 0.4, &seeds, 500));
+///
+/// // An empty soup is a still life from the start.
+/// assert_eq!(stabilization_histogram(12, 0.0, &[9], 10), vec![Some(0)]);
+/// ```
+pub fn stabilization_histogram(size: usize,
+                               density: f64,
+                               seeds: &[u64],
+                               max_gens: usize) -> Vec<Option<usize>> {
+    let num_threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let chunk_len = seeds.len().div_ceil(num_threads).max(1);
+    return thread::scope(|scope| {
+        let handles: Vec<_> = seeds.chunks(chunk_len).map(|chunk| {
+            scope.spawn(move || {
+                chunk.iter().map(|&seed| {
+                    find_stabilization(&random_board(size, size, density, seed), max_gens).map(|(gen, _)| gen)
+                }).collect::<Vec<Option<usize>>>()
+            })
+        }).collect();
+        return handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    })
+}
+
+/// Advance a board by one generation on the current thread, under an arbitrary rule such as a
+/// `Rule` or a `RangeRule`.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rule` - the rule deciding births and survivals.
+///
+/// # Returns
+/// The next generation.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::{rule::{RangeRule, Rule}, utils::{next_generation, next_generation_with_rule, random_board}};
+///
+/// let brd = random_board(20, 20, 0.4, 3);
+/// let conway = RangeRule { survive_min: 2, survive_max: 3, birth_min: 3, birth_max: 3 };
+/// assert_eq!(next_generation_with_rule(&brd, &conway), next_generation(&brd));
+/// assert_eq!(next_generation_with_rule(&brd, &Rule::conway()), next_generation(&brd));
+///
+/// // Cells with 1 to 4 neighbors survive, so a domino lives on.
+/// let lenient = RangeRule { survive_min: 1, survive_max: 4, birth_min: 3, birth_max: 4 };
+/// let domino = array![[0, 0, 0, 0], [0, 1, 1, 0], [0, 0, 0, 0]];
+/// assert_eq!(next_generation_with_rule(&domino, &lenient), domino);
+/// assert_eq!(next_generation(&domino), array![[0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]]);
+/// ```
+pub fn next_generation_with_rule<S: ndarray::Data<Elem = u8>, R: Transition>(brd: &ndarray::ArrayBase<S, ndarray::Ix2>, rule: &R) -> ndarray::Array2<u8> {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    let mut next: ndarray::Array2<u8> = brd.to_owned();
+    for (r, c, v) in capture_moves_with_rule(brd, &rows, &cols, &0, &rows, &0, &cols, rule) { next[[r, c]] = v; }
+    return next
+}
+
+/// Find where a run enters a cycle, by remembering every board seen.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `max_gens` - the most generations to step.
+///
+/// # Returns
+/// The length of the transient before the cycle (the generation of the first board that repeats)
+/// and the period of the cycle, or `None` if no board repeats within `max_gens` generations.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use game_of_life::utils::cycle_structure;
+///
+/// assert_eq!(cycle_structure(&array![[0, 1, 0], [0, 1, 0], [0, 1, 0]], 10), Some((0, 2)));
+///
+/// // A T-tetromino evolves for a while before settling into a period 2 traffic light.
+/// let mut t: Array2<u8> = Array2::zeros((15, 15));
+/// for &(r, c) in &[(6, 6), (6, 7), (6, 8), (7, 7)] { t[[r, c]] = 1; }
+/// assert_eq!(cycle_structure(&t, 100), Some((9, 2)));
+/// ```
+pub fn cycle_structure(initial: &ndarray::Array2<u8>, max_gens: usize) -> Option<(usize, usize)> {
+    let mut seen: std::collections::HashMap<ndarray::Array2<u8>, usize> = std::collections::HashMap::new();
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for gen in 0..=max_gens {
+        if let Some(&first) = seen.get(&brd) { return Some((first, gen - first)); }
+        let next = next_generation(&brd);
+        seen.insert(brd, gen);
+        brd = next;
+    }
+    return None
+}
+
+/// Split the live cells of a board into groups that touch, including diagonally.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The cells of each group, in row-major order. Groups are ordered by their first cell.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::connected_components;
+///
+/// let brd = array![[1, 1, 0, 0],
+///                  [0, 0, 1, 0],
+///                  [0, 0, 0, 0],
+///                  [1, 0, 0, 1]];
+/// assert_eq!(connected_components(&brd), vec![vec![(0, 0), (0, 1), (1, 2)], vec![(3, 0)], vec![(3, 3)]]);
+/// ```
+pub fn connected_components(brd: &ndarray::Array2<u8>) -> Vec<Vec<(usize, usize)>> {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    let mut visited: ndarray::Array2<bool> = ndarray::Array2::from_elem((rows, cols), false);
+    let mut components: Vec<Vec<(usize, usize)>> = vec![];
+    for (r, c) in iproduct!(0..rows, 0..cols) {
+        if (brd[[r, c]] != 1) | visited[[r, c]] { continue; }
+        // Flood fill from the first unvisited live cell.
+        visited[[r, c]] = true;
+        let mut stack: Vec<(usize, usize)> = vec![(r, c)];
+        let mut component: Vec<(usize, usize)> = vec![];
+        while let Some((cr, cc)) = stack.pop() {
+            component.push((cr, cc));
+            for nr in cr.saturating_sub(1)..(cr + 2).min(rows) {
+                for nc in cc.saturating_sub(1)..(cc + 2).min(cols) {
+                    if (brd[[nr, nc]] == 1) & !visited[[nr, nc]] {
+                        visited[[nr, nc]] = true;
+                        stack.push((nr, nc));
+                    }
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+    return components
+}
+
+/// A still life found by `classify_still_lifes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StillLifeKind {
+    Block,
+    Beehive,
+    Loaf,
+    Boat,
+    /// A still life that isn't one of the shapes above.
+    Unknown,
+}
+
+impl StillLifeKind {
+    /// The known shapes, each in one orientation.
+    fn known() -> Vec<(StillLifeKind, ndarray::Array2<u8>)> {
+        return vec![
+            (StillLifeKind::Block, ndarray::array![[1, 1], [1, 1]]),
+            (StillLifeKind::Beehive, ndarray::array![[0, 1, 1, 0], [1, 0, 0, 1], [0, 1, 1, 0]]),
+            (StillLifeKind::Loaf, ndarray::array![[0, 1, 1, 0], [1, 0, 0, 1], [0, 1, 0, 1], [0, 0, 1, 0]]),
+            (StillLifeKind::Boat, ndarray::array![[1, 1, 0], [1, 0, 1], [0, 1, 0]]),
+        ]
+    }
+}
+
+/// Catalog the still lifes on a board, such as the ash left once a soup has settled. Each group of
+/// touching cells is stepped on its own, and kept if it doesn't change. Groups that are not still
+/// lifes on their own, including oscillators and halves of a pseudo still life, are left out.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The kind of each still life and the top left corner of its bounding box, ordered as in
+/// `connected_components`.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::{classify_still_lifes, StillLifeKind};
+///
+/// let mut brd: Array2<u8> = Array2::zeros((12, 12));
+/// for &(r, c) in &[(1, 1), (1, 2), (2, 1), (2, 2)] { brd[[r, c]] = 1; }
+/// for &(r, c) in &[(5, 7), (6, 6), (6, 8), (7, 6), (7, 8), (8, 7)] { brd[[r, c]] = 1; }
+/// for &(r, c) in &[(10, 1), (10, 2), (10, 3)] { brd[[r, c]] = 1; }
+/// assert_eq!(classify_still_lifes(&brd), vec![(StillLifeKind::Block, (1, 1)),
+///                                             (StillLifeKind::Beehive, (5, 6))]);
+///
+/// let mut pond: Array2<u8> = Array2::zeros((6, 6));
+/// for &(r, c) in &[(1, 2), (1, 3), (2, 1), (2, 4), (3, 1), (3, 4), (4, 2), (4, 3)] { pond[[r, c]] = 1; }
+/// assert_eq!(classify_still_lifes(&pond), vec![(StillLifeKind::Unknown, (1, 1))]);
+/// ```
+pub fn classify_still_lifes(brd: &ndarray::Array2<u8>) -> Vec<(StillLifeKind, (usize, usize))> {
+    let mut found: Vec<(StillLifeKind, (usize, usize))> = vec![];
+    for component in connected_components(brd) {
+        // A margin of one cell leaves room for any births around it.
+        let (alone, corner) = isolate(&component, 1);
+        if next_generation(&alone) != alone { continue; }
+        found.push((still_life_kind(&alone), corner));
+    }
+    return found
+}
+
+/// Draw a group of cells on its own board.
+///
+/// # Arguments
+/// `component` - the cells, as from `connected_components`.
+/// `margin` - the number of dead cells around the group's bounding box.
+///
+/// # Returns
+/// The board, and the top left corner of the group's bounding box on the original board.
+fn isolate(component: &[(usize, usize)], margin: usize) -> (ndarray::Array2<u8>, (usize, usize)) {
+    let r0 = component.iter().map(|&(r, _)| r).min().unwrap();
+    let c0 = component.iter().map(|&(_, c)| c).min().unwrap();
+    let r1 = component.iter().map(|&(r, _)| r).max().unwrap();
+    let c1 = component.iter().map(|&(_, c)| c).max().unwrap();
+    let mut alone: ndarray::Array2<u8> = ndarray::Array2::zeros((r1 - r0 + 1 + 2 * margin, c1 - c0 + 1 + 2 * margin));
+    for &(r, c) in component { alone[[r - r0 + margin, c - c0 + margin]] = 1; }
+    return (alone, (r0, c0))
+}
+
+/// Match a still life against the known shapes, in any orientation.
+fn still_life_kind(brd: &ndarray::Array2<u8>) -> StillLifeKind {
+    let shape = crop(brd);
+    return StillLifeKind::known().into_iter()
+                                 .find(|(_, pattern)| Symmetry::ALL.iter().any(|&sym| transform(pattern, sym) == shape))
+                                 .map_or(StillLifeKind::Unknown, |(kind, _)| kind)
+}
+
+/// Put a pattern in a standard position and orientation, so that copies of it anywhere on a
+/// board, rotated or reflected, come out the same. The pattern is cropped to its bounding box and
+/// the orientation whose cells, read row by row, sort first is picked; of orientations that read
+/// the same, the one with fewer rows is picked.
+///
+/// # Arguments
+/// `brd` - the board holding the pattern.
+///
+/// # Returns
+/// The canonical form of the pattern, which is 0x0 if the board is empty.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use game_of_life::utils::{canonical_form, transform, Symmetry};
+///
+/// let mut glider: Array2<u8> = Array2::zeros((10, 10));
+/// for &(r, c) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] { glider[[r, c]] = 1; }
+/// let mirrored = transform(&glider, Symmetry::FlipHorizontal);
+/// assert_ne!(mirrored, glider);
+/// assert_eq!(canonical_form(&mirrored), canonical_form(&glider));
+/// assert_eq!(canonical_form(&glider), array![[0, 0, 1], [1, 0, 1], [0, 1, 1]]);
+///
+/// assert_eq!(canonical_form(&array![[0, 1], [0, 1], [0, 1]]), array![[1, 1, 1]]);
+/// ```
+pub fn canonical_form(brd: &ndarray::Array2<u8>) -> ndarray::Array2<u8> {
+    let cropped = crop(brd);
+    return Symmetry::ALL.iter()
+        .map(|&sym| transform(&cropped, sym))
+        .min_by(|a, b| (a.iter().collect::<Vec<_>>(), a.dim()).cmp(&(b.iter().collect::<Vec<_>>(), b.dim())))
+        .unwrap()
+}
+
+/// Which changes to a pattern `canonical_pattern_hash` ignores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymmetryInvariance {
+    /// Only where the pattern is on the board.
+    Translation,
+    /// Where the pattern is, and any rotation or reflection of it.
+    Dihedral,
+}
+
+/// Hash a pattern so that copies of it elsewhere on a board, or on another board, hash the same,
+/// for deduplicating a catalog of discovered patterns.
+///
+/// # Arguments
+/// `brd` - the board holding the pattern.
+/// `invariant` - whether rotations and reflections of the pattern also hash the same.
+///
+/// # Returns
+/// The hash of the live cells, cropped to their bounding box and, for `Dihedral`, put in their
+/// `canonical_form`. The hash is 64-bit FNV-1a of the rows and columns, as 8 little-endian bytes
+/// each, followed by the cells in row-major order, so it is the same on every build and platform
+/// and can be stored.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::{canonical_pattern_hash, transform, Symmetry, SymmetryInvariance};
+///
+/// let mut glider: Array2<u8> = Array2::zeros((10, 10));
+/// for &(r, c) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] { glider[[r, c]] = 1; }
+/// let mut moved: Array2<u8> = Array2::zeros((8, 12));
+/// for &(r, c) in &[(4, 7), (5, 8), (6, 6), (6, 7), (6, 8)] { moved[[r, c]] = 1; }
+/// let hash = canonical_pattern_hash(&glider, SymmetryInvariance::Translation);
+/// assert_eq!(hash, canonical_pattern_hash(&moved, SymmetryInvariance::Translation));
+///
+/// let mut blinker: Array2<u8> = Array2::zeros((10, 10));
+/// for &(r, c) in &[(4, 3), (4, 4), (4, 5)] { blinker[[r, c]] = 1; }
+/// assert_ne!(hash, canonical_pattern_hash(&blinker, SymmetryInvariance::Translation));
+///
+/// let turned = transform(&glider, Symmetry::Rotate90);
+/// assert_ne!(hash, canonical_pattern_hash(&turned, SymmetryInvariance::Translation));
+/// assert_eq!(canonical_pattern_hash(&glider, SymmetryInvariance::Dihedral),
+///            canonical_pattern_hash(&turned, SymmetryInvariance::Dihedral));
+///
+/// // Stored hashes stay valid.
+/// assert_eq!(hash, 0x16b6fbdf1ab1e31a);
+/// ```
+pub fn canonical_pattern_hash(brd: &ndarray::Array2<u8>, invariant: SymmetryInvariance) -> u64 {
+    let cropped = crop(brd);
+    let canonical = match invariant {
+        SymmetryInvariance::Translation => cropped,
+        SymmetryInvariance::Dihedral => canonical_form(&cropped),
+    };
+    let (rows, cols) = canonical.dim();
+    let dims = [(rows as u64).to_le_bytes(), (cols as u64).to_le_bytes()];
+    let bytes = dims.iter().flatten().chain(canonical.iter());
+    return bytes.fold(0xcbf29ce484222325, |hash: u64, &byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Stream the live neighbor count of every cell, without building a second board.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// An iterator over each cell's position and live neighbor count, in row-major order.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::iter_neighbor_counts;
+///
+/// let brd = array![[1, 1, 0],
+///                  [0, 1, 0]];
+/// let counts: Vec<((usize, usize), u8)> = iter_neighbor_counts(&brd).collect();
+/// assert_eq!(counts, vec![((0, 0), 2), ((0, 1), 2), ((0, 2), 2),
+///                         ((1, 0), 3), ((1, 1), 2), ((1, 2), 2)]);
+/// ```
+pub fn iter_neighbor_counts(brd: &ndarray::Array2<u8>) -> impl Iterator<Item = ((usize, usize), u8)> + '_ {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    return iproduct!(0..rows, 0..cols).map(move |(r, c)| ((r, c), count_neighbors(brd, &rows, &cols, &r, &c)))
+}
+
+/// Advance a board of a Generations rule by one generation. Only live cells (1) count as
+/// neighbors; dying cells (2 and up) age by one state each generation until they are dead.
+///
+/// # Arguments
+/// `brd` - the board, with cells from 0 to `rule.states() - 1`.
+/// `rule` - the rule.
+///
+/// # Returns
+/// The next generation.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::{rule::GenerationsRule, utils::{next_generation, step_generations}};
+///
+/// let brain: GenerationsRule = "B2/S/C3".parse().unwrap();
+/// let brd = array![[0, 0, 0, 0],
+///                  [0, 1, 1, 0],
+///                  [0, 2, 2, 0],
+///                  [0, 0, 0, 0]];
+/// assert_eq!(step_generations(&brd, &brain), array![[0, 1, 1, 0],
+///                                                   [0, 2, 2, 0],
+///                                                   [0, 0, 0, 0],
+///                                                   [0, 0, 0, 0]]);
+///
+/// // With 2 states this is the plain B/S rule.
+/// let blinker = array![[0, 1, 0], [0, 1, 0], [0, 1, 0]];
+/// assert_eq!(step_generations(&blinker, &GenerationsRule::default()), next_generation(&blinker));
+/// ```
+pub fn step_generations(brd: &ndarray::Array2<u8>, rule: &GenerationsRule) -> ndarray::Array2<u8> {
+    let alive: ndarray::Array2<u8> = brd.mapv(|v| (v == 1) as u8);
+    let mut next: ndarray::Array2<u8> = ndarray::Array2::zeros(brd.dim());
+    for ((r, c), count) in iter_neighbor_counts(&alive) {
+        next[[r, c]] = rule.next_state(brd[[r, c]], count);
+    }
+    return next
+}
+
+/// Advance a board of Brian's Brain by one generation. Cells are 0 for off, 1 for on and 2 for
+/// dying: an off cell turns on when exactly two of its neighbors are on, an on cell starts dying
+/// and a dying cell turns off. Only on cells count as neighbors. This is `step_generations` with
+/// `GenerationsRule::brians_brain`.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The next generation.
+///
+/// ```
+/// use ndarray::{array, s, Array2};
+/// use game_of_life::utils::step_brians_brain;
+///
+/// // Two on cells trailed by two dying cells fly one cell right each generation.
+/// let mut brd: Array2<u8> = Array2::zeros((4, 9));
+/// brd.slice_mut(s![1..3, 0..2]).assign(&array![[2, 1], [2, 1]]);
+/// for gen in 1..=5 {
+///     brd = step_brians_brain(&brd);
+///     let mut expected: Array2<u8> = Array2::zeros((4, 9));
+///     expected.slice_mut(s![1..3, gen..gen + 2]).assign(&array![[2, 1], [2, 1]]);
+///     assert_eq!(brd, expected);
+/// }
+/// ```
+pub fn step_brians_brain(brd: &ndarray::Array2<u8>) -> ndarray::Array2<u8> {
+    return step_generations(brd, &GenerationsRule::brians_brain())
+}
+
+/// Find the center of mass of the live cells. Following it across generations shows how fast and
+/// in which direction a pattern drifts.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The mean row and mean column of the live cells, or `None` if there are none.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::centroid;
+///
+/// let mut brd: Array2<u8> = Array2::zeros((6, 6));
+/// assert_eq!(centroid(&brd), None);
+/// for &(r, c) in &[(2, 3), (2, 4), (3, 3), (3, 4)] { brd[[r, c]] = 1; }
+/// assert_eq!(centroid(&brd), Some((2.5, 3.5)));
+/// ```
+pub fn centroid(brd: &ndarray::Array2<u8>) -> Option<(f64, f64)> {
+    let cells = live_cells(brd);
+    if cells.is_empty() { return None; }
+    let n = cells.len() as f64;
+    let r_sum: usize = cells.iter().map(|&(r, _)| r).sum();
+    let c_sum: usize = cells.iter().map(|&(_, c)| c).sum();
+    return Some((r_sum as f64 / n, c_sum as f64 / n))
+}
+
+/// Advance a board by one generation by surrounding it with a border of dead cells, so every
+/// cell's neighbors can be summed the same way, without special cases for edges and corners.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The next generation, the same as `next_generation`.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::{next_generation, random_board, step_padded};
+///
+/// for seed in 0..20 {
+///     let brd: Array2<u8> = random_board(2 + seed as usize, 17, 0.35, seed);
+///     assert_eq!(step_padded(&brd), next_generation(&brd));
+/// }
+/// ```
+pub fn step_padded(brd: &ndarray::Array2<u8>) -> ndarray::Array2<u8> {
+    use ndarray::s;
+
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    let mut padded: ndarray::Array2<u8> = ndarray::Array2::zeros((rows + 2, cols + 2));
+    padded.slice_mut(s![1..rows + 1, 1..cols + 1]).assign(brd);
+
+    // Add up the board shifted towards each of the eight neighbors.
+    let mut next: ndarray::Array2<u8> = ndarray::Array2::zeros((rows, cols));
+    for (dr, dc) in iproduct!(0..3, 0..3) {
+        if (dr, dc) != (1, 1) { next += &padded.slice(s![dr..dr + rows, dc..dc + cols]); }
+    }
+    ndarray::Zip::from(&mut next).and(brd).apply(|count, &v| {
+        *count = Rule::conway().next_state(v == 1, *count) as u8;
+    });
+    return next
+}
+
+/// A board could not be parsed from a string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardParseError {
+    /// A cell is not `0` or `1`.
+    InvalidCell { row: usize, col: usize, found: char },
+    /// A row has a different number of cells than the first row.
+    Ragged { row: usize, expected: usize, found: usize },
+    /// There are no cells.
+    Empty,
+}
+
+impl fmt::Display for BoardParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardParseError::InvalidCell { row, col, found } =>
+                write!(f, "invalid cell '{}' at row {}, column {}, expected 0 or 1", found, row, col),
+            BoardParseError::Ragged { row, expected, found } =>
+                write!(f, "row {} has {} cells, expected {}", row, found, expected),
+            BoardParseError::Empty => write!(f, "board has no cells"),
+        }
+    }
+}
+
+impl std::error::Error for BoardParseError {}
+
+/// Parse a small board written inline, with `/` between rows and one `0` or `1` per cell.
+///
+/// # Arguments
+/// `text` - the board, e.g. `010/111/100`.
+///
+/// # Returns
+/// The board, or an error if a cell is invalid or the rows differ in length.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{board_from_string, BoardParseError};
+///
+/// assert_eq!(board_from_string("010/111/100").unwrap(), array![[0, 1, 0], [1, 1, 1], [1, 0, 0]]);
+/// assert_eq!(board_from_string("010/11"), Err(BoardParseError::Ragged { row: 1, expected: 3, found: 2 }));
+/// assert_eq!(board_from_string("01x"), Err(BoardParseError::InvalidCell { row: 0, col: 2, found: 'x' }));
+/// assert_eq!(board_from_string(""), Err(BoardParseError::Empty));
+/// ```
+pub fn board_from_string(text: &str) -> Result<ndarray::Array2<u8>, BoardParseError> {
+    let lines: Vec<&str> = text.trim().split('/').collect();
+    let cols = lines[0].chars().count();
+    if cols == 0 { return Err(BoardParseError::Empty); }
+    let mut cells: Vec<u8> = Vec::with_capacity(lines.len() * cols);
+    for (row, line) in lines.iter().enumerate() {
+        let found = line.chars().count();
+        if found != cols { return Err(BoardParseError::Ragged { row, expected: cols, found }); }
+        for (col, ch) in line.chars().enumerate() {
+            match ch {
+                '0' => cells.push(0),
+                '1' => cells.push(1),
+                _ => return Err(BoardParseError::InvalidCell { row, col, found: ch }),
+            }
+        }
+    }
+    return Ok(ndarray::Array2::from_shape_vec((lines.len(), cols), cells).unwrap())
+}
+
+/// An object left on a board once it has settled, found by `settle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AshObject {
+    StillLife(StillLifeKind),
+    /// An oscillator, such as a blinker with period 2.
+    Oscillator { period: usize },
+    /// Something that doesn't repeat on its own within `ASH_MAX_PERIOD` generations, such as a
+    /// spaceship or half of an object that depends on its neighbors.
+    Unknown,
+}
+
+/// The longest period `settle` looks for when classifying an oscillator.
+pub static ASH_MAX_PERIOD: usize = 30;
+
+/// What a board settles into, from `settle`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettleResult {
+    /// The first board of the cycle the run settles into, or the last board if it didn't settle.
+    pub board: ndarray::Array2<u8>,
+    /// The generation the cycle starts at, or `None` if the run didn't settle.
+    pub generation: Option<usize>,
+    /// The period of the cycle, 1 for a still life, or `None` if the run didn't settle.
+    pub period: Option<usize>,
+    /// The objects on the settled board and the top left corners of their bounding boxes, ordered
+    /// as in `connected_components`. Empty if the run didn't settle.
+    pub objects: Vec<(AshObject, (usize, usize))>,
+}
+
+impl SettleResult {
+    /// Whether the run settled into a still life or an oscillator.
+    pub fn settled(&self) -> bool {
+        return self.period.is_some()
+    }
+}
+
+/// Run a board until it settles into a still life or an oscillator, and catalog the objects
+/// left over, the "ash".
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `max_gens` - the most generations to step.
+///
+/// # Returns
+/// The settled board, when and with what period it settled, and what it is made of. If no board
+/// repeats within `max_gens` generations, the board after `max_gens` generations with no
+/// generation, period or objects.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::{settle, AshObject, StillLifeKind};
+///
+/// // An L-tromino that grows into a block, next to a blinker.
+/// let mut brd: Array2<u8> = Array2::zeros((12, 12));
+/// for &(r, c) in &[(1, 1), (1, 2), (2, 1), (8, 5), (8, 6), (8, 7)] { brd[[r, c]] = 1; }
+/// let result = settle(&brd, 100);
+/// assert!(result.settled());
+/// assert_eq!((result.generation, result.period), (Some(1), Some(2)));
+/// assert_eq!(result.objects, vec![(AshObject::StillLife(StillLifeKind::Block), (1, 1)),
+///                                 (AshObject::Oscillator { period: 2 }, (7, 6))]);
+///
+/// let mut glider: Array2<u8> = Array2::zeros((20, 20));
+/// for &(r, c) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] { glider[[r, c]] = 1; }
+/// let result = settle(&glider, 10);
+/// assert!(!result.settled());
+/// assert_eq!(result.board, game_of_life::utils::advance(glider, 10));
+/// assert!(result.objects.is_empty());
+/// ```
+pub fn settle(initial: &ndarray::Array2<u8>, max_gens: usize) -> SettleResult {
+    let (generation, period) = match cycle_structure(initial, max_gens) {
+        Some((generation, period)) => (generation, period),
+        None => return SettleResult { board: advance(initial.to_owned(), max_gens), generation: None,
+                                      period: None, objects: vec![] },
+    };
+    let board = advance(initial.to_owned(), generation);
+
+    let mut objects: Vec<(AshObject, (usize, usize))> = vec![];
+    for component in connected_components(&board) {
+        // Leave room for oscillators to grow as they cycle.
+        let (alone, corner) = isolate(&component, 3);
+        let object = match exact_period(&alone, ASH_MAX_PERIOD) {
+            Some(1) => AshObject::StillLife(still_life_kind(&alone)),
+            Some(period) => AshObject::Oscillator { period },
+            None => AshObject::Unknown,
+        };
+        objects.push((object, corner));
+    }
+    return SettleResult { board, generation: Some(generation), period: Some(period), objects }
+}
+
+/// Draw a board as an SVG image: a white background with a black square for each live cell.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `cell_size` - the side length of each cell, in SVG user units.
+///
+/// # Returns
+/// The SVG document.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::board_svg;
+///
+/// let glider = array![[0, 1, 0],
+///                     [0, 0, 1],
+///                     [1, 1, 1]];
+/// let svg = board_svg(&glider, 10);
+/// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"30\""));
+/// // One for the background, and one per live cell.
+/// assert_eq!(svg.matches("<rect").count(), 6);
+/// assert!(svg.contains("<rect x=\"10\" y=\"0\" width=\"10\" height=\"10\"/>"));
+/// ```
+pub fn board_svg(brd: &ndarray::Array2<u8>, cell_size: usize) -> String {
+    use std::fmt::Write;
+
+    let width = brd.shape()[1] * cell_size;
+    let height = brd.shape()[0] * cell_size;
+    let mut out = String::new();
+    writeln!(out, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+             width, height, width, height).unwrap();
+    writeln!(out, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>").unwrap();
+    writeln!(out, "<g fill=\"black\">").unwrap();
+    for (r, c) in live_cells(brd) {
+        writeln!(out, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+                 c * cell_size, r * cell_size, cell_size, cell_size).unwrap();
+    }
+    writeln!(out, "</g>").unwrap();
+    writeln!(out, "</svg>").unwrap();
+    return out
+}
+
+/// Save a board as an SVG file, see `board_svg`. Unlike a raster image, it stays sharp at any
+/// size, so it suits slides and documents.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `path` - the file to write.
+/// `cell_size` - the side length of each cell, in SVG user units.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::save_svg;
+///
+/// let path = std::env::temp_dir().join("game_of_life_save_svg.svg");
+/// save_svg(&array![[1, 0], [0, 1]], &path, 8).unwrap();
+/// assert_eq!(std::fs::read_to_string(&path).unwrap().matches("<rect").count(), 3);
+/// ```
+pub fn save_svg<P: AsRef<std::path::Path>>(brd: &ndarray::Array2<u8>, path: P, cell_size: usize) -> std::io::Result<()> {
+    return std::fs::write(path, board_svg(brd, cell_size))
+}
+
+/// Mark the cells that differ between two boards, such as two generations, to show what changed.
+///
+/// # Arguments
+/// `a` - the first board.
+/// `b` - the second board, of the same shape.
+///
+/// # Returns
+/// A board with 1 where the boards differ and 0 where they match, or an error if their shapes
+/// differ. This is `combine` with `CombineOp::Xor`.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::xor_board;
+///
+/// let a = array![[0, 1, 0], [1, 1, 0]];
+/// let b = array![[0, 1, 1], [0, 1, 0]];
+/// assert_eq!(xor_board(&a, &b), Ok(array![[0, 0, 1], [1, 0, 0]]));
+/// assert!(xor_board(&a, &array![[0, 1]]).is_err());
+/// ```
+pub fn xor_board(a: &ndarray::Array2<u8>, b: &ndarray::Array2<u8>) -> Result<ndarray::Array2<u8>, ShapeMismatch> {
+    return combine(a, b, CombineOp::Xor)
+}
+
+/// Score how worth a closer look a soup is, to skip the many that quickly die out or freeze.
+///
+/// The score is `lifetime * (1 + variation) + escapes`, from 0 up to 3, where:
+/// - `lifetime` is the fraction of the `gens` generations before the board settles into a still
+///   life or oscillator (or dies out), from `cycle_structure`, so 1 if it never settles.
+/// - `variation` is the coefficient of variation (standard deviation over mean) of the population
+///   before the board settles, capped at 1. Boards that boom and bust score higher than ones that
+///   slowly burn out.
+/// - `escapes` is 1 if a spaceship such as a glider breaks away from the rest at any point before
+///   the board settles, and 0 otherwise.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `gens` - the most generations to run.
+///
+/// # Returns
+/// The score; higher is more interesting.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::interestingness;
+///
+/// let mut dying: Array2<u8> = Array2::zeros((10, 10));
+/// for &(r, c) in &[(2, 2), (6, 7)] { dying[[r, c]] = 1; }
+/// assert!(interestingness(&dying, 100) < 0.05);
+///
+/// let mut block: Array2<u8> = Array2::zeros((10, 10));
+/// for &(r, c) in &[(4, 4), (4, 5), (5, 4), (5, 5)] { block[[r, c]] = 1; }
+/// assert_eq!(interestingness(&block, 100), 0.0);
+///
+/// let mut glider: Array2<u8> = Array2::zeros((30, 30));
+/// for &(r, c) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] { glider[[r, c]] = 1; }
+/// assert!(interestingness(&glider, 100) > 1.0);
+/// ```
+pub fn interestingness(initial: &ndarray::Array2<u8>, gens: usize) -> f64 {
+    if gens == 0 { return 0.0; }
+    let settled_at = cycle_structure(initial, gens).map_or(gens, |(tail, _)| tail);
+    let lifetime = settled_at as f64 / gens as f64;
+
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    let mut populations: Vec<f64> = vec![];
+    let mut escapes = 0.0;
+    for _ in 0..settled_at {
+        populations.push(population(&brd) as f64);
+        // Small margins keep the search cheap, and leave room for a glider to move.
+        if escapes == 0.0 {
+            let moving = connected_components(&brd).iter().any(|component| {
+                detect_spaceship(&isolate(component, 4).0, 4, Boundary::Fixed).is_some()
+            });
+            if moving { escapes = 1.0; }
+        }
+        brd = next_generation(&brd);
+    }
+
+    let variation = if populations.is_empty() { 0.0 } else {
+        let n = populations.len() as f64;
+        let mean = populations.iter().sum::<f64>() / n;
+        let variance = populations.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / n;
+        if mean == 0.0 { 0.0 } else { (variance.sqrt() / mean).min(1.0) }
+    };
+    return lifetime * (1.0 + variation) + escapes
+}
+
+/// Which nearby cells count as a cell's neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    /// Every cell within `radius` rows and columns, the 8 surrounding cells for radius 1.
+    Moore,
+    /// Every cell within `radius` steps up, down, left or right, the 4 orthogonal cells for
+    /// radius 1.
+    VonNeumann,
+}
+
+impl Default for Neighborhood {
+    fn default() -> Neighborhood {
+        return Neighborhood::Moore
+    }
+}
+
+impl Neighborhood {
+    /// The offsets of the neighbors of a cell.
+    ///
+    /// # Arguments
+    /// `radius` - how far away neighbors may be.
+    pub fn offsets(&self, radius: usize) -> Vec<(i64, i64)> {
+        let radius = radius as i64;
+        return iproduct!(-radius..=radius, -radius..=radius)
+            .filter(|&(dr, dc)| (dr, dc) != (0, 0))
+            .filter(|&(dr, dc)| match self {
+                Neighborhood::Moore => true,
+                Neighborhood::VonNeumann => dr.abs() + dc.abs() <= radius,
+            })
+            .collect()
+    }
+}
+
+/// Everything that decides how a board steps, for `step`. The default is Conway's game of life on
+/// a board with dead cells beyond its edges, the same as `next_generation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepConfig {
+    pub rule: Rule,
+    pub boundary: Boundary,
+    pub neighborhood: Neighborhood,
+    /// How far away neighbors may be, 1 for the cells touching a cell. A `Rule` only gives
+    /// births and survivals for up to 8 neighbors, so with a radius above 1 a cell with more live
+    /// neighbors than that always dies.
+    pub radius: usize,
+}
+
+impl Default for StepConfig {
+    fn default() -> StepConfig {
+        return StepConfig { rule: Rule::conway(), boundary: Boundary::Fixed, neighborhood: Neighborhood::Moore, radius: 1 }
+    }
+}
+
+impl StepConfig {
+    /// Whether this is plain Moore neighborhood stepping with dead cells beyond the edges, as done
+    /// by the other steppers, with any rule.
+    pub fn is_standard(&self) -> bool {
+        return (self.boundary == Boundary::Fixed) & (self.neighborhood == Neighborhood::Moore) & (self.radius == 1)
+    }
+}
+
+/// Advance a board by one generation on the current thread, with a configurable rule, boundary
+/// and neighborhood.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `config` - how the board steps.
+///
+/// # Returns
+/// The next generation.
+///
+/// # Panics
+/// If the neighborhood has more than 255 cells, or the radius is 0.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use game_of_life::utils::{next_generation, random_board, step, Boundary, Neighborhood, StepConfig};
+///
+/// let soup = random_board(12, 9, 0.4, 3);
+/// assert_eq!(StepConfig::default().radius, 1);
+/// assert_eq!(step(&soup, &StepConfig::default()), next_generation(&soup));
+///
+/// // On a torus, a blinker across the edge wraps around.
+/// let torus = StepConfig { boundary: Boundary::Torus, ..Default::default() };
+/// let mut blinker: Array2<u8> = Array2::zeros((5, 5));
+/// for &(r, c) in &[(2, 4), (2, 0), (2, 1)] { blinker[[r, c]] = 1; }
+/// let next = step(&blinker, &torus);
+/// assert_eq!(game_of_life::utils::live_cells(&next), vec![(1, 0), (2, 0), (3, 0)]);
+///
+/// // With the four orthogonal neighbors, a dead cell between three live ones is born.
+/// let von_neumann = StepConfig { neighborhood: Neighborhood::VonNeumann, ..Default::default() };
+/// assert_eq!(step(&array![[0, 1, 0], [1, 0, 1], [0, 0, 0]], &von_neumann), array![[0, 0, 0], [0, 1, 0], [0, 0, 0]]);
+///
+/// // With radius 2, the middle of a full board has 24 live neighbors, too many to survive.
+/// let wide = StepConfig { radius: 2, rule: "B3/S012345678".parse().unwrap(), ..Default::default() };
+/// assert_eq!(step(&Array2::ones((5, 5)), &wide)[[2, 2]], 0);
+/// ```
+pub fn step(brd: &ndarray::Array2<u8>, config: &StepConfig) -> ndarray::Array2<u8> {
+    assert!(config.radius > 0, "radius must be at least 1");
+    let offsets = config.neighborhood.offsets(config.radius);
+    assert!(offsets.len() <= u8::MAX as usize, "{} neighbors is too many to count", offsets.len());
+
+    let mut neighbors: Vec<(i64, i64)> = vec![(0, 0); offsets.len()];
+    return ndarray::Array2::from_shape_fn(brd.dim(), |(r, c)| {
+        for (neighbor, &(dr, dc)) in neighbors.iter_mut().zip(offsets.iter()) {
+            *neighbor = (r as i64 + dr, c as i64 + dc);
+        }
+        let count = gather_board_values_with_boundary(brd, &neighbors, config.boundary);
+        return config.rule.next_state(brd[[r, c]] == 1, count) as u8
+    })
+}
+
+/// Advance a board by one generation with noise: a cell that Conway's rules would bring to life
+/// or keep alive only does so with probability `p`, and is dead otherwise.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `p` - the chance that a birth or survival happens.
+/// `rng` - the source of randomness. A seeded one makes the run reproducible.
+///
+/// # Returns
+/// The next generation.
+///
+/// # Panics
+/// If `p` is not between 0 and 1.
+///
+/// ```
+/// use rand::{rngs::StdRng, SeedableRng};
+/// use game_of_life::utils::{next_generation, population, random_board, step_stochastic};
+///
+/// let soup = random_board(20, 20, 0.4, 5);
+/// let mut rng = StdRng::seed_from_u64(1);
+/// assert_eq!(step_stochastic(&soup, 1.0, &mut rng), next_generation(&soup));
+/// assert_eq!(population(&step_stochastic(&soup, 0.0, &mut rng)), 0);
+///
+/// let noisy = step_stochastic(&soup, 0.5, &mut StdRng::seed_from_u64(2));
+/// assert_eq!(noisy, step_stochastic(&soup, 0.5, &mut StdRng::seed_from_u64(2)));
+/// assert!(population(&noisy) < population(&next_generation(&soup)));
+/// ```
+pub fn step_stochastic<R: rand::Rng>(brd: &ndarray::Array2<u8>, p: f64, rng: &mut R) -> ndarray::Array2<u8> {
+    assert!((0.0..=1.0).contains(&p), "probability {} is not between 0 and 1", p);
+    let mut next = next_generation(brd);
+    for v in next.iter_mut() {
+        if (*v == 1) & !rng.gen_bool(p) { *v = 0; }
+    }
+    return next
+}
+
+/// Count how many cells have each number of live neighbors.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The number of cells with 0, 1, and so on up to 8 live neighbors.
+///
+/// ```
+/// use ndarray::Array2;
+/// use game_of_life::utils::neighbor_histogram;
+///
+/// let mut brd: Array2<u8> = Array2::zeros((10, 10));
+/// brd[[4, 4]] = 1;
+/// assert_eq!(neighbor_histogram(&brd), [92, 8, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+pub fn neighbor_histogram(brd: &ndarray::Array2<u8>) -> [usize; 9] {
+    let mut histogram = [0; 9];
+    for (_, count) in iter_neighbor_counts(brd) { histogram[count as usize] += 1; }
+    return histogram
+}
+
+/// A checkpoint could not be written or read.
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// The checkpoint directory could not be created or listed.
+    Io(std::io::Error),
+    /// A checkpoint could not be written.
+    Save(csv::Error),
+    /// A checkpoint could not be read back.
+    Load(BoardLoadError),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckpointError::Io(e) => write!(f, "cannot access checkpoints: {}", e),
+            CheckpointError::Save(e) => write!(f, "cannot save checkpoint: {}", e),
+            CheckpointError::Load(e) => write!(f, "cannot load checkpoint: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<std::io::Error> for CheckpointError {
+    fn from(e: std::io::Error) -> CheckpointError {
+        return CheckpointError::Io(e)
+    }
+}
+
+/// The file name of the checkpoint for a generation. The generation is zero padded so the names
+/// sort in order.
+fn checkpoint_name(generation: usize) -> String {
+    return format!("checkpoint_{:010}.csv", generation)
+}
+
+/// Run the simulation on the current thread, saving the board to a directory every so often so a
+/// long run can be picked up again with `resume_from` after a crash. Each checkpoint is a CSV
+/// board, as from `save_board`, named after its generation. It is written to a temporary file
+/// first and then renamed, so a crash while saving never leaves a half-written checkpoint.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `generations` - the number of generations to step.
+/// `every` - the number of generations between checkpoints.
+/// `dir` - the directory to save checkpoints in. It is created if needed.
+///
+/// # Returns
+/// The board after `generations` generations, or an error if a checkpoint could not be saved.
+///
+/// # Panics
+/// If `every` is 0.
+///
+/// ```
+/// use game_of_life::utils::{advance, random_board, resume_from, run_with_checkpoints};
+///
+/// let dir = std::env::temp_dir().join("game_of_life_checkpoints");
+/// let _ = std::fs::remove_dir_all(&dir);
+/// let soup = random_board(16, 16, 0.4, 11);
+///
+/// // Pretend the run crashed after 25 generations, leaving checkpoints up to generation 20.
+/// run_with_checkpoints(&soup, 25, 10, &dir).unwrap();
+/// let (generation, board) = resume_from(&dir).unwrap().unwrap();
+/// assert_eq!(generation, 20);
+/// assert_eq!(advance(board, 40 - generation), advance(soup, 40));
+///
+/// assert!(resume_from(std::env::temp_dir().join("game_of_life_no_checkpoints")).unwrap().is_none());
+/// ```
+pub fn run_with_checkpoints<P: AsRef<std::path::Path>>(initial: &ndarray::Array2<u8>,
+                                                       generations: usize,
+                                                       every: usize,
+                                                       dir: P) -> Result<ndarray::Array2<u8>, CheckpointError> {
+    assert!(every > 0, "checkpoints must be at least 1 generation apart");
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for gen in 1..=generations {
+        brd = next_generation(&brd);
+        if gen % every == 0 {
+            let tmp = dir.join(format!("{}.tmp", checkpoint_name(gen)));
+            save_board(&brd, &tmp).map_err(CheckpointError::Save)?;
+            std::fs::rename(&tmp, dir.join(checkpoint_name(gen)))?;
+            info!("saved checkpoint for generation {}", gen);
+        }
+    }
+    return Ok(brd)
+}
+
+/// Load the latest checkpoint saved by `run_with_checkpoints`.
+///
+/// # Arguments
+/// `dir` - the directory the checkpoints were saved in.
+///
+/// # Returns
+/// The generation and board of the latest checkpoint, `None` if there are no checkpoints, or an
+/// error if the directory or checkpoint could not be read.
+pub fn resume_from<P: AsRef<std::path::Path>>(dir: P) -> Result<Option<(usize, ndarray::Array2<u8>)>, CheckpointError> {
+    let dir = dir.as_ref();
+    if !dir.exists() { return Ok(None); }
+    let mut latest: Option<usize> = None;
+    for entry in std::fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let generation = name.to_str()
+                             .and_then(|name| name.strip_prefix("checkpoint_")?.strip_suffix(".csv")?.parse().ok());
+        if let Some(generation) = generation { latest = latest.max(Some(generation)); }
+    }
+    return match latest {
+        Some(generation) => {
+            let brd = load_board(dir.join(checkpoint_name(generation))).map_err(CheckpointError::Load)?;
+            Ok(Some((generation, brd)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// The sum of the six neighbors of a cell on a hexagonal grid, stored in a board in "odd-r"
+/// offset coordinates: each row is a row of hexagons, and odd rows are shifted half a cell to the
+/// right. So a cell on an even row touches the cells above and below it and those one column to
+/// their left, while a cell on an odd row touches the cells above and below it and those one
+/// column to their right. Cells beyond the edges are dead.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rows` - the number of rows.
+/// `cols` - the number of columns.
+/// `r` - the row of the cell.
+/// `c` - the column of the cell.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::count_neighbors_hex;
+///
+/// let arr = array![[1, 2, 3],
+///                  [4, 5, 6],
+///                  [7, 8, 9]];
+/// // Row 1 is odd: above are 2 and 3, beside are 4 and 6, below are 8 and 9.
+/// assert_eq!(count_neighbors_hex(&arr, &3, &3, &1, &1), 2 + 3 + 4 + 6 + 8 + 9);
+/// // Row 2 is even: above are 4 and 5, beside are 7 and 9.
+/// assert_eq!(count_neighbors_hex(&arr, &3, &3, &2, &1), 4 + 5 + 7 + 9);
+/// // Row 0 is even: beside is 2 and below is 4, as the cells to their left are off the board.
+/// assert_eq!(count_neighbors_hex(&arr, &3, &3, &0, &0), 2 + 4);
+/// ```
+pub fn count_neighbors_hex(brd: &ndarray::Array2<u8>,
+                           rows: &usize,
+                           cols: &usize,
+                           r: &usize,
+                           c: &usize) -> u8 {
+    debug_assert!((r < rows) & (c < cols));
+    let (r, c) = (*r as i64, *c as i64);
+    // The column of the diagonal neighbors above and below, besides the one straight up or down.
+    let dc = if r % 2 == 0 { -1 } else { 1 };
+    return gather_board_values_with_boundary(brd, &[
+        (r - 1, c),
+        (r - 1, c + dc),
+        (r, c - 1),
+        (r, c + 1),
+        (r + 1, c),
+        (r + 1, c + dc)], Boundary::Fixed)
+}
+
+/// Advance a hexagonal board by one generation, see `count_neighbors_hex` for how the board is
+/// laid out.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rule` - the rule, with neighbor counts from 0 to 6, such as `B2/S34`.
+///
+/// # Returns
+/// The next generation.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::{rule::Rule, utils::step_hex};
+///
+/// let hex_life: Rule = "B2/S34".parse().unwrap();
+/// // A dead cell between two live ones on the row above is born.
+/// let brd = array![[0, 1, 1, 0],
+///                  [0, 0, 0, 0],
+///                  [0, 0, 0, 0]];
+/// assert_eq!(step_hex(&brd, &hex_life), array![[0, 0, 0, 0],
+///                                              [0, 1, 0, 0],
+///                                              [0, 0, 0, 0]]);
+/// ```
+pub fn step_hex<R: Transition>(brd: &ndarray::Array2<u8>, rule: &R) -> ndarray::Array2<u8> {
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    return ndarray::Array2::from_shape_fn((rows, cols), |(r, c)| {
+        let count = count_neighbors_hex(brd, &rows, &cols, &r, &c);
+        return rule.next_state(brd[[r, c]] == 1, count) as u8
+    })
+}
+
+/// Save the rows of a one-dimensional run as a space-time image, with time going down: row `t`
+/// of the image is step `t` of the run, drawn as `rgb_frame` draws a board.
+///
+/// # Arguments
+/// `rows` - the state at each step, as from `step_elementary`. Shorter rows are padded with dead
+/// cells on the right.
+/// `path` - the image file to write. Its extension picks the format, PNG or BMP.
+/// `scale` - the side length of each cell in pixels.
+///
+/// # Returns
+/// Nothing, or an error if the image can't be written.
+///
+/// ```
+/// use game_of_life::utils::{spacetime_image, step_elementary};
+///
+/// let mut rows: Vec<Vec<u8>> = vec![vec![0; 31]];
+/// rows[0][15] = 1;
+/// for _ in 0..15 { rows.push(step_elementary(rows.last().unwrap(), 30)); }
+///
+/// let path = std::env::temp_dir().join("game_of_life_rule_30.png");
+/// spacetime_image(&rows, &path, 3).unwrap();
+/// let image = image::open(&path).unwrap();
+/// assert_eq!((image.width(), image.height()), (31 * 3, 16 * 3));
+/// ```
+pub fn spacetime_image<P: AsRef<std::path::Path>>(rows: &[Vec<u8>], path: P, scale: usize) -> Result<(), image::ImageError> {
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut diagram: ndarray::Array2<u8> = ndarray::Array2::zeros((rows.len(), width));
+    for (t, row) in rows.iter().enumerate() {
+        for (c, &v) in row.iter().enumerate() { diagram[[t, c]] = v; }
+    }
+    let frame = rgb_frame(&diagram, scale);
+    let image = image::RgbImage::from_raw((width * scale) as u32, (rows.len() * scale) as u32, frame).unwrap();
+    return image.save(path)
+}
+
+/// The most cells `has_predecessor_bruteforce` can search, one bit of a `u64` per cell.
+pub static PREDECESSOR_MAX_CELLS: usize = 63;
+
+/// A board has too many cells for a brute force search.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PredecessorError {
+    /// The board has more cells than the caller's limit.
+    OverLimit { cells: usize, max_cells: usize },
+    /// The board has more cells than `PREDECESSOR_MAX_CELLS`, whatever the limit.
+    TooLarge { cells: usize },
+}
+
+impl fmt::Display for PredecessorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PredecessorError::OverLimit { cells, max_cells } =>
+                write!(f, "board has {} cells, more than the limit of {}", cells, max_cells),
+            PredecessorError::TooLarge { cells } =>
+                write!(f, "board has {} cells, more than the {} that can be searched", cells, PREDECESSOR_MAX_CELLS),
+        }
+    }
+}
+
+impl std::error::Error for PredecessorError {}
+
+/// Search every board of the same shape for one whose next generation is `brd`. A board with no
+/// predecessor is a Garden of Eden. There are `2^n` candidates for a board of `n` cells, so this is
+/// only practical for tiny boards.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `max_cells` - the largest board to search.
+///
+/// # Returns
+/// The first predecessor found, or `None` if there is none. Or an error if the board has more than
+/// `max_cells` cells, or more than `PREDECESSOR_MAX_CELLS`.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{has_predecessor_bruteforce, next_generation, population, PredecessorError};
+///
+/// let blinker = array![[0, 0, 0],
+///                      [1, 1, 1],
+///                      [0, 0, 0]];
+/// let pred = has_predecessor_bruteforce(&blinker, 9).unwrap().unwrap();
+/// assert_eq!(next_generation(&pred), blinker);
+///
+/// // A full 2x2 board comes from any three of its cells.
+/// let full = array![[1, 1], [1, 1]];
+/// assert_eq!(population(&has_predecessor_bruteforce(&full, 4).unwrap().unwrap()), 3);
+///
+/// // On a 2x2 board every cell neighbors the other three, so cells can't live alone.
+/// assert_eq!(has_predecessor_bruteforce(&array![[0, 0], [0, 1]], 4).unwrap(), None);
+/// assert_eq!(has_predecessor_bruteforce(&ndarray::Array2::zeros((4, 4)), 9),
+///            Err(PredecessorError::OverLimit { cells: 16, max_cells: 9 }));
+/// assert_eq!(has_predecessor_bruteforce(&ndarray::Array2::zeros((8, 8)), 100),
+///            Err(PredecessorError::TooLarge { cells: 64 }));
+/// ```
+pub fn has_predecessor_bruteforce(brd: &ndarray::Array2<u8>, max_cells: usize) -> Result<Option<ndarray::Array2<u8>>, PredecessorError> {
+    let cells = brd.len();
+    if cells > max_cells { return Err(PredecessorError::OverLimit { cells, max_cells }); }
+    if cells > PREDECESSOR_MAX_CELLS { return Err(PredecessorError::TooLarge { cells }); }
+
+    let mut candidate: ndarray::Array2<u8> = ndarray::Array2::zeros(brd.dim());
+    for bits in 0u64..1 << cells {
+        for (i, v) in candidate.iter_mut().enumerate() { *v = ((bits >> i) & 1) as u8; }
+        if next_generation(&candidate) == *brd { return Ok(Some(candidate)); }
+    }
+    return Ok(None)
+}
+
+/// Find the largest rectangle of dead cells, for placing a new pattern without touching the ones
+/// already on the board. Each row in turn is taken as the bottom of the rectangle, with the run of
+/// dead cells above each column as a histogram, whose largest rectangle is found with a stack.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The rectangle as (ri, rf, ci, cf), like `bounding_box`, or (0, 0, 0, 0) if every cell is alive.
+/// Of rectangles of the same area, the one found first is returned.
+///
+/// ```
+/// use ndarray::{array, s, Array2};
+/// use game_of_life::utils::{largest_empty_rectangle, population};
+///
+/// let mut brd: Array2<u8> = Array2::zeros((5, 5));
+/// brd[[2, 2]] = 1;
+/// let (ri, rf, ci, cf) = largest_empty_rectangle(&brd);
+/// assert_eq!((rf - ri) * (cf - ci), 10);
+/// assert_eq!(population(&brd.slice(s![ri..rf, ci..cf]).to_owned()), 0);
+///
+/// let brd = array![[1, 0, 0, 1],
+///                  [0, 0, 0, 1],
+///                  [1, 0, 0, 0]];
+/// assert_eq!(largest_empty_rectangle(&brd), (0, 3, 1, 3));
+/// assert_eq!(largest_empty_rectangle(&array![[1, 1]]), (0, 0, 0, 0));
+/// ```
+pub fn largest_empty_rectangle(brd: &ndarray::Array2<u8>) -> (usize, usize, usize, usize) {
+    let cols = brd.shape()[1];
+    let mut heights: Vec<usize> = vec![0; cols];
+    let (mut best, mut best_area) = ((0, 0, 0, 0), 0);
+    for (r, row) in brd.genrows().into_iter().enumerate() {
+        for (h, &v) in heights.iter_mut().zip(row) { *h = if v == 0 { *h + 1 } else { 0 }; }
+
+        // Columns whose heights are increasing; each one's rectangle ends where a lower one starts.
+        let mut stack: Vec<usize> = vec![];
+        for c in 0..=cols {
+            let h = if c < cols { heights[c] } else { 0 };
+            while let Some(&top) = stack.last() {
+                if heights[top] < h { break; }
+                stack.pop();
+                let start = stack.last().map_or(0, |&s| s + 1);
+                let area = heights[top] * (c - start);
+                if area > best_area {
+                    best_area = area;
+                    best = (r + 1 - heights[top], r + 1, start, c);
+                }
+            }
+            stack.push(c);
+        }
+    }
+    return best
+}
+
+/// Advance a board by one generation with a rule chosen per cell, so different regions of the
+/// board follow different rules. Neighbors are counted across the borders between regions as
+/// anywhere else, with the edges of the board dead.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `rule_map` - for each cell, the index into `rules` of the rule it follows.
+/// `rules` - the rules.
+///
+/// # Returns
+/// The next generation.
+///
+/// # Panics
+/// If `rule_map` is not the shape of `brd`, or refers to a rule that isn't in `rules`.
+///
+/// ```
+/// use ndarray::{s, Array2};
+/// use game_of_life::{rule::Rule, utils::{next_generation_with_rule, step_multirule}};
+///
+/// // Conway on the left, HighLife on the right, each half holding a cell with 6 live neighbors.
+/// let mut brd: Array2<u8> = Array2::zeros((5, 12));
+/// for &c0 in &[1, 7] {
+///     brd.slice_mut(s![1..4, c0..c0 + 3]).fill(1);
+///     brd.slice_mut(s![1..4, c0 + 1]).fill(0);
+/// }
+/// let mut rule_map: Array2<usize> = Array2::zeros((5, 12));
+/// rule_map.slice_mut(s![.., 6..]).fill(1);
+/// let highlife: Rule = "B36/S23".parse().unwrap();
+/// let next = step_multirule(&brd, &rule_map, &[Rule::conway(), highlife]);
+///
+/// assert_eq!(next[[2, 2]], 0);
+/// assert_eq!(next[[2, 8]], 1);
+/// assert_eq!(next.slice(s![.., ..6]), next_generation_with_rule(&brd, &Rule::conway()).slice(s![.., ..6]));
+/// assert_eq!(next.slice(s![.., 6..]), next_generation_with_rule(&brd, &highlife).slice(s![.., 6..]));
+/// ```
+pub fn step_multirule(brd: &ndarray::Array2<u8>, rule_map: &ndarray::Array2<usize>, rules: &[Rule]) -> ndarray::Array2<u8> {
+    assert_eq!(brd.dim(), rule_map.dim(), "the rule map must be the shape of the board");
+    let rows = brd.shape()[0];
+    let cols = brd.shape()[1];
+    return ndarray::Array2::from_shape_fn((rows, cols), |(r, c)| {
+        let rule = rules.get(rule_map[[r, c]]).expect("the rule map refers to a missing rule");
+        let count = count_neighbors(brd, &rows, &cols, &r, &c);
+        return rule.next_state(brd[[r, c]] == 1, count) as u8
+    })
+}
+
+/// What becomes of a pattern in the long run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fate {
+    /// Every cell dies.
+    Extinct,
+    /// The board stops changing.
+    StillLife,
+    /// The board repeats with this period.
+    Oscillator(usize),
+    /// The board reappears shifted, see `Spaceship`.
+    Spaceship { period: usize, dr: i64, dc: i64 },
+    /// The population and the live region are still growing at the end of the run.
+    Growing,
+    /// None of the above within the generations run.
+    Unknown,
+}
+
+/// Run a pattern and say what it does: die out, settle into a still life or an oscillator, fly
+/// off as a spaceship, or keep growing. The edges of the board are dead, so a spaceship is only
+/// seen as one if the board is large enough that it doesn't reach the edge, and growth is only
+/// seen until the pattern fills the board.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `max_gens` - the most generations to step. A spaceship is looked for, and growth measured,
+/// over the second half of the run, to let the pattern settle first.
+///
+/// # Returns
+/// The fate of the pattern.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use game_of_life::utils::{classify_fate, Fate};
+///
+/// let blinker = array![[0, 1, 0], [0, 1, 0], [0, 1, 0]];
+/// assert_eq!(classify_fate(&blinker, 10), Fate::Oscillator(2));
+///
+/// let mut glider: Array2<u8> = Array2::zeros((40, 40));
+/// for &(r, c) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] { glider[[r, c]] = 1; }
+/// assert_eq!(classify_fate(&glider, 40), Fate::Spaceship { period: 4, dr: 1, dc: 1 });
+///
+/// let block = array![[0, 0, 0, 0], [0, 1, 1, 0], [0, 1, 1, 0], [0, 0, 0, 0]];
+/// assert_eq!(classify_fate(&block, 10), Fate::StillLife);
+/// assert_eq!(classify_fate(&array![[0, 1], [0, 1]], 10), Fate::Extinct);
+/// ```
+pub fn classify_fate(initial: &ndarray::Array2<u8>, max_gens: usize) -> Fate {
+    if let Some((transient, period)) = cycle_structure(initial, max_gens) {
+        let brd = advance(initial.to_owned(), transient);
+        return if population(&brd) == 0 { Fate::Extinct }
+               else if period == 1 { Fate::StillLife }
+               else { Fate::Oscillator(period) }
+    }
+
+    let half = max_gens / 2;
+    let middle = advance(initial.to_owned(), half);
+    if let Some(Spaceship { period, dr, dc }) = detect_spaceship(&middle, max_gens - half, Boundary::Fixed) {
+        return Fate::Spaceship { period, dr, dc }
+    }
+    let end = advance(middle.clone(), max_gens - half);
+    let area = |brd: &ndarray::Array2<u8>| bounding_box(brd).map_or(0, |(ri, rf, ci, cf)| (rf - ri) * (cf - ci));
+    if (population(&end) > population(&middle)) & (area(&end) > area(&middle)) { return Fate::Growing; }
+    return Fate::Unknown
+}
+
+/// Run the simulation with rules that change over time, stepping each generation with the rule
+/// the schedule gives for it.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `generations` - the number of generations to step.
+/// `schedule` - the rules.
+///
+/// # Returns
+/// The board after the last generation.
+///
+/// ```
+/// use game_of_life::{rule::{Rule, RuleSchedule}, utils::{next_generation_with_rule, random_board, run_with_schedule}};
+///
+/// let highlife: Rule = "B36/S23".parse().unwrap();
+/// let schedule = RuleSchedule::new(Rule::conway()).switch_at(5, highlife);
+/// let initial = random_board(24, 24, 0.4, 189);
+///
+/// // Five Conway generations, then HighLife from there.
+/// let mut expected = initial.clone();
+/// for _ in 0..5 { expected = next_generation_with_rule(&expected, &Rule::conway()); }
+/// assert_eq!(run_with_schedule(&initial, 5, &schedule), expected);
+/// for _ in 0..7 { expected = next_generation_with_rule(&expected, &highlife); }
+/// assert_eq!(run_with_schedule(&initial, 12, &schedule), expected);
+/// ```
+pub fn run_with_schedule(initial: &ndarray::Array2<u8>, generations: usize, schedule: &RuleSchedule) -> ndarray::Array2<u8> {
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for gen in 0..generations { brd = next_generation_with_rule(&brd, schedule.rule_at(gen)); }
+    return brd
+}
+
+/// Count the live cells in each row.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The population of each row, from the top.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::row_profile;
+///
+/// assert_eq!(row_profile(&array![[0, 0, 0], [1, 1, 1], [0, 1, 0]]), vec![0, 3, 1]);
+/// ```
+pub fn row_profile(brd: &ndarray::Array2<u8>) -> Vec<usize> {
+    return brd.genrows().into_iter().map(|row| row.iter().filter(|&&v| v == 1).count()).collect()
+}
+
+/// Count the live cells in each column.
+///
+/// # Arguments
+/// `brd` - the board.
+///
+/// # Returns
+/// The population of each column, from the left.
+///
+/// ```
+/// use ndarray::{s, Array2};
+/// use game_of_life::utils::column_profile;
+///
+/// let mut line: Array2<u8> = Array2::zeros((4, 5));
+/// line.slice_mut(s![.., 3]).fill(1);
+/// assert_eq!(column_profile(&line), vec![0, 0, 0, 4, 0]);
+/// ```
+pub fn column_profile(brd: &ndarray::Array2<u8>) -> Vec<usize> {
+    return brd.gencolumns().into_iter().map(|col| col.iter().filter(|&&v| v == 1).count()).collect()
+}
+
+/// Run the simulation for at most a given wall-clock time as well as a number of generations, so
+/// a huge board or an untrusted input can't hold up a server. The clock is checked after every
+/// generation, which costs little next to stepping even a small board, so the run stops within
+/// one generation of the timeout.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `max_gens` - the most generations to step.
+/// `timeout` - the most time to spend stepping.
+///
+/// # Returns
+/// The board reached and the number of generations stepped to reach it.
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use game_of_life::utils::{random_board, run_with_timeout};
+///
+/// let big = random_board(300, 300, 0.4, 191);
+/// let start = Instant::now();
+/// let (_, gens) = run_with_timeout(&big, usize::MAX, Duration::from_millis(200));
+/// assert!(gens < usize::MAX);
+/// assert!(start.elapsed() >= Duration::from_millis(200));
+/// assert!(start.elapsed() < Duration::from_secs(5));
+///
+/// let (brd, gens) = run_with_timeout(&big, 2, Duration::from_secs(60));
+/// assert_eq!((brd, gens), (game_of_life::utils::advance(big, 2), 2));
+/// ```
+pub fn run_with_timeout(initial: &ndarray::Array2<u8>,
+                        max_gens: usize,
+                        timeout: std::time::Duration) -> (ndarray::Array2<u8>, usize) {
+    let start = std::time::Instant::now();
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    let mut gen: usize = 0;
+    while (gen < max_gens) & (start.elapsed() < timeout) {
+        brd = next_generation(&brd);
+        gen += 1;
+    }
+    return (brd, gen)
+}
+
+/// Find the cells of a starting board that could have influenced a cell some generations later.
+/// A cell only sees its neighbors each generation, so this is every cell within `generations` of
+/// it in each direction: its past light cone. No stepping is needed.
+///
+/// # Arguments
+/// `r` - the row of the cell.
+/// `c` - the column of the cell.
+/// `generations` - the number of generations between the starting board and the cell.
+/// `rows` - the number of rows of the board.
+/// `cols` - the number of columns of the board.
+///
+/// # Returns
+/// The positions of the cells, in row-major order, clamped to the board.
+///
+/// ```
+/// use game_of_life::utils::ancestry;
+///
+/// let cone = ancestry(50, 50, 4, 100, 100);
+/// assert_eq!(cone.len(), (2 * 4 + 1) * (2 * 4 + 1));
+/// assert_eq!((cone[0], cone[cone.len() - 1]), ((46, 46), (54, 54)));
+///
+/// assert_eq!(ancestry(0, 1, 1, 3, 3), vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]);
+/// assert_eq!(ancestry(2, 2, 0, 5, 5), vec![(2, 2)]);
+/// ```
+pub fn ancestry(r: usize, c: usize, generations: usize, rows: usize, cols: usize) -> Vec<(usize, usize)> {
+    let r_range = r.saturating_sub(generations)..(r + generations + 1).min(rows);
+    let c_range = c.saturating_sub(generations)..(c + generations + 1).min(cols);
+    return iproduct!(r_range, c_range).collect()
+}
+
+/// Check a run against a recorded one, to use a saved run as a test oracle.
+///
+/// # Arguments
+/// `initial` - the starting board.
+/// `golden` - the recorded boards, starting with generation 0, which should be `initial`.
+///
+/// # Returns
+/// Nothing if stepping `initial` gives every recorded board in turn, or the first generation that
+/// doesn't match.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::utils::{next_generation, verify_against_golden};
+///
+/// let blinker = array![[0, 1, 0], [0, 1, 0], [0, 1, 0]];
+/// let mut golden = vec![blinker.clone()];
+/// for _ in 0..4 { golden.push(next_generation(golden.last().unwrap())); }
+/// assert_eq!(verify_against_golden(&blinker, &golden), Ok(()));
+///
+/// golden[3][[0, 0]] = 1;
+/// assert_eq!(verify_against_golden(&blinker, &golden), Err(3));
+/// assert_eq!(verify_against_golden(&array![[1]], &golden), Err(0));
+/// ```
+pub fn verify_against_golden(initial: &ndarray::Array2<u8>, golden: &[ndarray::Array2<u8>]) -> Result<(), usize> {
+    let mut brd: ndarray::Array2<u8> = initial.to_owned();
+    for (gen, expected) in golden.iter().enumerate() {
+        if gen > 0 { brd = next_generation(&brd); }
+        if brd != *expected { return Err(gen); }
+    }
+    return Ok(())
+}
+
+/// Show a named pattern some generations on, such as a pulsar at phase 2. The pattern is placed
+/// with `generations` dead cells around it, exactly the region `reachable_bounds` says it can
+/// reach, so the edges of the board never affect it.
+///
+/// # Arguments
+/// `name` - the name of one of the `pattern::PATTERNS`.
+/// `generations` - the number of generations to step.
+///
+/// # Returns
+/// The board after `generations` generations, or `None` if there is no pattern of that name.
+///
+/// ```
+/// use game_of_life::utils::{crop, pattern_at_generation};
+///
+/// let phase0 = pattern_at_generation("blinker", 0).unwrap();
+/// let phase1 = pattern_at_generation("blinker", 1).unwrap();
+/// let phase2 = pattern_at_generation("blinker", 2).unwrap();
+/// assert_ne!(crop(&phase1), crop(&phase0));
+/// assert_eq!(crop(&phase2), crop(&phase0));
+///
+/// // The glider isn't stopped by the edge of the board.
+/// let glider = pattern_at_generation("glider", 40).unwrap();
+/// assert_eq!(crop(&glider), crop(&pattern_at_generation("glider", 0).unwrap()));
+/// assert!(pattern_at_generation("teapot", 1).is_none());
+/// ```
+pub fn pattern_at_generation(name: &str, generations: usize) -> Option<ndarray::Array2<u8>> {
+    use ndarray::s;
+
+    let pattern = crate::pattern::Pattern::named(name)?.board();
+    let (rows, cols) = pattern.dim();
+    let mut brd: ndarray::Array2<u8> = ndarray::Array2::zeros((rows + 2 * generations, cols + 2 * generations));
+    brd.slice_mut(s![generations..generations + rows, generations..generations + cols]).assign(&pattern);
+    debug_assert_eq!(reachable_bounds(&brd, generations), (0, brd.shape()[0], 0, brd.shape()[1]));
+    return Some(advance(brd, generations))
+}
+
+/// Write text onto a board in a bitmap font, one cell per pixel, with a dead column between
+/// characters. Characters the font doesn't have are left blank.
+///
+/// # Arguments
+/// `text` - the text, on one line.
+/// `font` - the font, such as `font::FONT_5X7`.
+///
+/// # Returns
+/// A board as tall as the font and just wide enough for the text.
+///
+/// ```
+/// use ndarray::array;
+/// use game_of_life::{font::FONT_5X7, utils::board_from_banner};
+///
+/// assert_eq!(board_from_banner("T", &FONT_5X7), array![[1, 1, 1, 1, 1],
+///                                                      [0, 0, 1, 0, 0],
+///                                                      [0, 0, 1, 0, 0],
+///                                                      [0, 0, 1, 0, 0],
+///                                                      [0, 0, 1, 0, 0],
+///                                                      [0, 0, 1, 0, 0],
+///                                                      [0, 0, 1, 0, 0]]);
+/// let hello = board_from_banner("HELLO", &FONT_5X7);
+/// assert_eq!(hello.dim(), (7, 5 * 6 - 1));
+/// assert_eq!(hello.column(5).sum(), 0);
+/// ```
+pub fn board_from_banner(text: &str, font: &crate::font::BitmapFont) -> ndarray::Array2<u8> {
+    let chars: Vec<char> = text.chars().collect();
+    let cols = (chars.len() * (font.width + 1)).saturating_sub(1);
+    let mut brd: ndarray::Array2<u8> = ndarray::Array2::zeros((font.height, cols));
+    for (i, &ch) in chars.iter().enumerate() {
+        let rows = match font.glyph(ch) { Some(rows) => rows, None => continue };
+        let c0 = i * (font.width + 1);
+        for (r, &bits) in rows.iter().enumerate().take(font.height) {
+            for c in 0..font.width {
+                brd[[r, c0 + c]] = (bits >> (font.width - 1 - c)) & 1;
+            }
+        }
+    }
+    return brd
+}
+
+/// Shrink a board to the fraction of live cells in each `block` by `block` tile of it, for a view
+/// of the large-scale structure of a board too big to show cell by cell.
+///
+/// # Arguments
+/// `brd` - the board.
+/// `block` - the width and height of each tile.
+///
+/// # Returns
+/// The density of each tile. Tiles at the bottom and right edges are cut short when the board
+/// isn't a multiple of `block` in size, and their density is over the cells they do cover.
+///
+/// # Panics
+/// If `block` is 0.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use game_of_life::utils::density_map;
+///
+/// let brd = array![[1, 1, 0, 0, 1],
+///                  [1, 0, 0, 0, 1],
+///                  [0, 0, 0, 1, 0]];
+/// assert_eq!(density_map(&brd, 2), array![[0.75, 0.0, 1.0],
+///                                         [0.0, 0.5, 0.0]]);
+///
+/// // The left half of the board is alive.
+/// let half: Array2<u8> = Array2::from_shape_fn((30, 40), |(_, c)| (c < 20) as u8);
+/// let densities = density_map(&half, 40);
+/// assert_eq!(densities.dim(), (1, 1));
+/// assert!((densities[[0, 0]] - 0.5).abs() < 1e-9);
+/// ```
+pub fn density_map(brd: &ndarray::Array2<u8>, block: usize) -> ndarray::Array2<f64> {
+    assert!(block > 0, "block must be at least 1");
+    let (rows, cols) = brd.dim();
+    let tiles = (rows.div_ceil(block), cols.div_ceil(block));
+    return ndarray::Array2::from_shape_fn(tiles, |(tr, tc)| {
+        let tile = brd.slice(ndarray::s![tr * block..((tr + 1) * block).min(rows),
+                                         tc * block..((tc + 1) * block).min(cols)]);
+        tile.iter().map(|&v| v as usize).sum::<usize>() as f64 / tile.len() as f64
+    })
+}